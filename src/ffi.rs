@@ -0,0 +1,73 @@
+//! Access to the SQLite C API, independently of how the extension is built.
+//!
+//! In WASM, the extension is linked with the SQLite implementation of
+//! `sqlite-wasm-rs`, whose `sqlite3_*` functions are called directly. As a
+//! native loadable extension (`loadable-ext` feature), the extension must not
+//! link against SQLite, and instead calls the routines of the host through
+//! the `sqlite3_api_routines` table it receives when loaded: the functions
+//! provided here by `libsqlite3-sys` dispatch through the pointers of that
+//! table, once registered by [`init`].
+//!
+//! The rest of the crate accesses the SQLite API through this module only,
+//! so that it works unchanged in both settings.
+
+use core::ffi::{c_char, c_int};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use libsqlite3_sys::*;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use sqlite_wasm_rs::*;
+
+/// Registers the SQLite API routines provided by the host, like the
+/// `SQLITE_EXTENSION_INIT2` macro of `sqlite3ext.h`.
+///
+/// Every other `sqlite3_*` call panics until this succeeds, so it must be
+/// the first thing done by an entry point.
+///
+/// # Arguments
+/// * `p_api` - Pointer to the SQLite API routines provided by the host.
+/// * `pz_err_msg` - Pointer to error message pointer, receiving a description
+///   of the failure, if any (see [`crate::set_error_message`]).
+///
+/// # Returns
+/// * `SQLITE_OK` on success, or `SQLITE_ERROR` if the routines are missing or
+///   belong to an older SQLite version than the one the extension targets.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `p_api` pointer,
+/// which must be null or point to valid API routines.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) unsafe fn init(
+    p_api: *const sqlite3_api_routines,
+    pz_err_msg: *mut *mut c_char,
+) -> c_int {
+    if p_api.is_null() {
+        return SQLITE_ERROR;
+    }
+    match unsafe { rusqlite_extension_init2(p_api.cast_mut()) } {
+        Ok(()) => SQLITE_OK,
+        Err(error) => {
+            let message = alloc::format!("failed to initialize the SQLite API: {error}");
+            unsafe { crate::set_error_message(pz_err_msg, &message) };
+            SQLITE_ERROR
+        }
+    }
+}
+
+/// Registers the SQLite API routines provided by the host, like the
+/// `SQLITE_EXTENSION_INIT2` macro of `sqlite3ext.h`.
+///
+/// In WASM, SQLite is linked directly, so there is nothing to register:
+/// `p_api` is ignored and may be null, as it is when the extension is
+/// registered through [`crate::register`] or [`crate::register_on`].
+///
+/// # Safety
+/// This function does not dereference its arguments; it is unsafe for parity
+/// with its native counterpart.
+#[cfg(target_arch = "wasm32")]
+pub(crate) unsafe fn init(
+    _p_api: *const sqlite3_api_routines,
+    _pz_err_msg: *mut *mut c_char,
+) -> c_int {
+    SQLITE_OK
+}
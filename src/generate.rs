@@ -0,0 +1,81 @@
+//! Safe Rust API generating UUIDs exactly like the SQL functions do.
+//!
+//! The SQL functions delegate to this module, so that host code can, for
+//! instance, pre-generate the ids of rows before inserting them, with the
+//! guarantee of getting the same kind of values as `uuid()`, `uuid_blob()`,
+//! `uuid7()` and `uuid7_blob()`.
+//!
+//! This does not hold on connections opened after
+//! [`register_with_seed`](crate::register_with_seed), whose `uuid()`,
+//! `uuid_blob()` and `uuid_series` draw their UUIDv4 from a seeded generator
+//! instead of this module.
+
+use alloc::string::{String, ToString};
+
+use uuid::{NoContext, Timestamp, Uuid};
+
+/// Largest millisecond timestamp of a UUIDv7, which is 48 bits wide.
+const MAX_V7_MILLIS: i64 = 0xFFFF_FFFF_FFFF;
+
+/// Generates a random UUIDv4, as `uuid()` and `uuid_blob()` do.
+pub(crate) fn new_v4() -> Uuid {
+    Uuid::new_v4()
+}
+
+/// Generates a UUIDv7 from the process-wide monotonic context, as `uuid7()`
+/// and `uuid7_blob()` do.
+///
+/// The `uuid` crate draws every [`Uuid::now_v7`] from a single shared
+/// `ContextV7`, which fills the bits following the millisecond timestamp with
+/// a counter that is incremented for UUIDs generated within the same
+/// millisecond. As a result, UUIDs returned by this function are strictly
+/// increasing within the process, both as bytes and as lowercase text.
+pub(crate) fn now_v7() -> Uuid {
+    Uuid::now_v7()
+}
+
+/// Generates a UUIDv7 embedding a millisecond Unix timestamp instead of the
+/// current time, as `uuid7_at(ms)` does, with random remaining bits.
+///
+/// Returns `None` if `millis` is not between 0 and 2^48 - 1, which
+/// [`Uuid::new_v7`] would otherwise silently truncate.
+pub(crate) fn v7_at_millis(millis: i64) -> Option<Uuid> {
+    if !(0..=MAX_V7_MILLIS).contains(&millis) {
+        return None;
+    }
+    let millis = u64::try_from(millis).unwrap();
+    let nanos = u32::try_from(millis % 1000).unwrap() * 1_000_000;
+    Some(Uuid::new_v7(Timestamp::from_unix(NoContext, millis / 1000, nanos)))
+}
+
+/// Returns a new random UUIDv4 as a canonical 36-character string, like
+/// `uuid()`.
+#[must_use]
+pub fn new_v4_string() -> String {
+    new_v4().to_string()
+}
+
+/// Returns a new random UUIDv4 as 16 bytes, like `uuid_blob()`.
+#[must_use]
+pub fn new_v4_blob() -> [u8; 16] {
+    new_v4().into_bytes()
+}
+
+/// Returns a new UUIDv7 as a canonical 36-character string, like `uuid7()`.
+///
+/// The UUIDv7 share their monotonic source with `uuid7()`, so values
+/// generated from Rust and SQL within the same process are strictly
+/// increasing, whichever side generated them.
+#[must_use]
+pub fn now_v7_string() -> String {
+    now_v7().to_string()
+}
+
+/// Returns a new UUIDv7 as 16 bytes, like `uuid7_blob()`.
+///
+/// The UUIDv7 share their monotonic source with `uuid7_blob()`, see
+/// [`now_v7_string`].
+#[must_use]
+pub fn now_v7_blob() -> [u8; 16] {
+    now_v7().into_bytes()
+}
@@ -1,28 +1,149 @@
 #![doc = include_str!("../README.md")]
+#![cfg(any(target_arch = "wasm32", feature = "loadable-ext"))]
 #![no_std]
 #![allow(clippy::similar_names)]
-#![cfg(target_arch = "wasm32")]
 
 extern crate alloc;
 
-use alloc::{ffi::CString, string::ToString};
+mod ffi;
+pub mod generate;
+mod parse_many;
+#[cfg(all(target_arch = "wasm32", feature = "rusqlite"))]
+pub mod rusqlite_helpers;
+#[cfg(target_arch = "wasm32")]
+mod seeded;
+mod series;
+mod test_vectors;
+#[cfg(feature = "v8")]
+mod v8;
+mod vtab;
+
+use alloc::{
+    boxed::Box,
+    ffi::CString,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::{
+    cmp::Ordering,
     ffi::{CStr, c_char, c_int, c_void},
+    fmt::Write,
     ptr, slice,
+    sync::atomic::{AtomicU32, Ordering as AtomicOrdering},
 };
 
-use sqlite_wasm_rs::{
-    SQLITE_BLOB, SQLITE_DETERMINISTIC, SQLITE_INNOCUOUS, SQLITE_OK, SQLITE_TEXT, SQLITE_TRANSIENT,
-    SQLITE_UTF8, sqlite3, sqlite3_api_routines, sqlite3_context, sqlite3_create_function_v2,
-    sqlite3_result_blob, sqlite3_result_null, sqlite3_result_text, sqlite3_value,
-    sqlite3_value_blob, sqlite3_value_bytes, sqlite3_value_text, sqlite3_value_type,
+use ffi::{
+    SQLITE_BLOB, SQLITE_BUSY, SQLITE_DETERMINISTIC, SQLITE_ERROR, SQLITE_INNOCUOUS, SQLITE_INTEGER,
+    SQLITE_LOCKED, SQLITE_MISUSE, SQLITE_NOMEM, SQLITE_NULL, SQLITE_OK, SQLITE_TEXT,
+    SQLITE_TRANSIENT, SQLITE_UTF8, sqlite3, sqlite3_aggregate_context, sqlite3_api_routines,
+    sqlite3_context, sqlite3_create_collation_v2, sqlite3_create_function_v2,
+    sqlite3_create_module_v2, sqlite3_malloc, sqlite3_result_blob, sqlite3_result_error,
+    sqlite3_result_error_nomem, sqlite3_result_int, sqlite3_result_int64, sqlite3_result_null,
+    sqlite3_result_text, sqlite3_user_data, sqlite3_value, sqlite3_value_blob, sqlite3_value_bytes,
+    sqlite3_value_int64, sqlite3_value_text, sqlite3_value_type,
 };
-use uuid::Uuid;
+use uuid::{NoContext, Timestamp, Uuid, timestamp::UUID_TICKS_BETWEEN_EPOCHS};
+use vtab::TableFunction;
+
+/// Prefix of the URN form of a UUID, matched case-insensitively.
+const URN_PREFIX: &str = "urn:uuid:";
+
+/// Helper function to parse a UUID from its textual representation.
+///
+/// Accepts all the standard textual forms, in any case: simple (32 hex
+/// digits), hyphenated, braced (`{...}`) and URN (`urn:uuid:...`). All the
+/// forms of a UUID parse to the same value, whatever its version, so that
+/// e.g. `uuid_blob` canonicalizes them to the same 16 bytes. Partial forms,
+/// such as misplaced hyphens or missing digits, are rejected.
+///
+/// # Arguments
+/// * `s` - The text to parse, e.g. a 32 (hex) or 36 (hyphenated) character
+///   string.
+///
+/// # Returns
+/// * `Option<Uuid>` - The parsed UUID if valid, or `None` if invalid.
+fn parse_uuid_str(s: &str) -> Option<Uuid> {
+    // `Uuid::parse_str` only accepts a lowercase URN prefix.
+    let s = match s.get(..URN_PREFIX.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(URN_PREFIX) => &s[URN_PREFIX.len()..],
+        _ => s,
+    };
+    Uuid::parse_str(s).ok()
+}
+
+/// Helper function to read an SQLite value as UTF-8 text.
+///
+/// Non-TEXT values are converted to text following the usual SQLite rules,
+/// while SQL `NULL` yields `None`.
+///
+/// # Arguments
+/// * `value` - Pointer to the sqlite3_value to read.
+///
+/// # Returns
+/// * `Option<&str>` - The text of the value, or `None` if it is `NULL` or not
+///   valid UTF-8.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `value` pointer.
+/// The returned string is only valid until the value is modified or freed.
+unsafe fn value_text<'a>(value: *mut sqlite3_value) -> Option<&'a str> {
+    let text_ptr = unsafe { sqlite3_value_text(value) };
+    if text_ptr.is_null() {
+        return None;
+    }
+    let len = usize::try_from(unsafe { sqlite3_value_bytes(value) }).ok()?;
+    let bytes = unsafe { slice::from_raw_parts(text_ptr, len) };
+    core::str::from_utf8(bytes).ok()
+}
+
+/// Helper function to read the content of an SQLite BLOB value.
+///
+/// # Arguments
+/// * `value` - Pointer to the sqlite3_value to read.
+///
+/// # Returns
+/// * `Option<&[u8]>` - The bytes of the BLOB, or `None` if the value is not a
+///   BLOB.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `value` pointer.
+/// The returned slice is only valid until the value is modified or freed.
+unsafe fn value_blob<'a>(value: *mut sqlite3_value) -> Option<&'a [u8]> {
+    if unsafe { sqlite3_value_type(value) } != SQLITE_BLOB {
+        return None;
+    }
+    let blob_ptr = unsafe { sqlite3_value_blob(value) };
+    let len = usize::try_from(unsafe { sqlite3_value_bytes(value) }).ok()?;
+    if blob_ptr.is_null() {
+        // Zero-length BLOBs are reported as a NULL pointer.
+        return Some(&[]);
+    }
+    Some(unsafe { slice::from_raw_parts(blob_ptr.cast::<u8>(), len) })
+}
+
+/// Helper function to read an SQLite INTEGER value.
+///
+/// # Arguments
+/// * `value` - Pointer to the sqlite3_value to read.
+///
+/// # Returns
+/// * `Option<i64>` - The integer, or `None` if the value is not an INTEGER.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `value` pointer.
+unsafe fn value_int64(value: *mut sqlite3_value) -> Option<i64> {
+    if unsafe { sqlite3_value_type(value) } != SQLITE_INTEGER {
+        return None;
+    }
+    Some(unsafe { sqlite3_value_int64(value) })
+}
 
 /// Helper function to parse a UUID from an SQLite argument value.
 ///
 /// Supports two input formats:
-/// - **TEXT**: A 32 (hex) or 36 (hyphenated) character string string.
+/// - **TEXT**: A 32 (hex) or 36 (hyphenated) character string, optionally
+///   wrapped in braces or prefixed by `urn:uuid:` (see [`parse_uuid_str`]).
 /// - **BLOB**: A raw 16-byte UUID buffer.
 ///
 /// # Arguments
@@ -41,15 +162,9 @@ unsafe fn parse_uuid_arg(argv: *mut *mut sqlite3_value, index: usize) -> Option<
     let ty = unsafe { sqlite3_value_type(arg) };
 
     match ty {
-        SQLITE_TEXT => {
-            let text_ptr = unsafe { sqlite3_value_text(arg) };
-            if text_ptr.is_null() {
-                return None;
-            }
-            let c_str = unsafe { CStr::from_ptr(text_ptr.cast::<c_char>()) };
-            let s = c_str.to_str().ok()?;
-            Uuid::parse_str(s).ok()
-        }
+        // Reads exactly `sqlite3_value_bytes` bytes, so that text with an
+        // embedded NUL byte is rejected rather than truncated.
+        SQLITE_TEXT => unsafe { value_text(arg) }.and_then(parse_uuid_str),
         SQLITE_BLOB => {
             let blob_ptr = unsafe { sqlite3_value_blob(arg) };
             let bytes = unsafe { sqlite3_value_bytes(arg) };
@@ -65,44 +180,338 @@ unsafe fn parse_uuid_arg(argv: *mut *mut sqlite3_value, index: usize) -> Option<
     }
 }
 
+/// Sets the result of a SQL function to a string.
+///
+/// A string with an interior NUL byte cannot be passed to SQLite as a
+/// NUL-terminated string, and panicking inside an `extern "C"` callback
+/// aborts the process: such a string raises an SQL error instead.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `ctx` pointer.
+unsafe fn result_text(ctx: *mut sqlite3_context, s: String) {
+    match CString::new(s) {
+        // `c_str` is dropped when this function returns, so SQLite must copy
+        // it: `SQLITE_STATIC` would leave the result dangling.
+        Ok(c_str) => unsafe {
+            sqlite3_result_text(ctx, c_str.as_ptr(), -1, SQLITE_TRANSIENT());
+        },
+        Err(_) => unsafe {
+            sqlite3_result_error(ctx, c"result contains a NUL byte".as_ptr(), -1);
+        },
+    }
+}
+
+/// Sets the result of a SQL function to the canonical 36-character string
+/// form of a UUID.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `ctx` pointer.
+unsafe fn result_uuid_text(ctx: *mut sqlite3_context, u: &Uuid) {
+    unsafe {
+        result_text(ctx, u.to_string()); // canonical 36-char string
+    }
+}
+
+/// Sets the result of a SQL function to the raw 16-byte BLOB form of a UUID.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `ctx` pointer.
+unsafe fn result_uuid_blob(ctx: *mut sqlite3_context, u: &Uuid) {
+    // The bytes borrow a `Uuid` which usually lives on the stack of the
+    // calling SQL function, while SQLite may read the result after it returns:
+    // `SQLITE_TRANSIENT` makes SQLite take its own copy of the 16 bytes, where
+    // `SQLITE_STATIC` would only be sound for a buffer outliving the statement.
+    let bytes = u.as_bytes();
+    unsafe {
+        sqlite3_result_blob(ctx, bytes.as_ptr().cast::<c_void>(), 16, SQLITE_TRANSIENT());
+    }
+}
+
+/// Options a connection was initialised with, attached by
+/// [`register_functions`] as the user data of each SQL function, so that
+/// later registrations on other connections do not affect it.
+#[derive(Debug, Clone, Copy)]
+struct Registration {
+    /// The families of functions registered on the connection.
+    set: FunctionSet,
+    /// Whether malformed UUID arguments raise an error, see
+    /// [`result_invalid_uuid`].
+    strict: bool,
+    /// Whether `uuid()`, `uuid_blob()` and `uuid_series` draw from a seeded
+    /// generator, see [`register_with_seed`].
+    seeded: bool,
+}
+
+/// Returns the [`Registration`] of the connection a SQL function was called
+/// on.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the user data of `ctx`,
+/// which must have been attached by [`register_functions`].
+unsafe fn registration(ctx: *mut sqlite3_context) -> Registration {
+    unsafe { *sqlite3_user_data(ctx).cast::<Registration>() }
+}
+
+/// Releases the [`Registration`] attached to a SQL function.
+///
+/// # Safety
+/// This function is unsafe because it takes ownership of a raw pointer, which
+/// must have been produced by `Box::into_raw` on a [`Registration`].
+unsafe extern "C" fn destroy_registration(p_app: *mut c_void) {
+    drop(unsafe { Box::from_raw(p_app.cast::<Registration>()) });
+}
+
+/// Sets the result of a SQL function whose UUID argument could not be parsed.
+///
+/// Functions registered by [`sqlite3_uuid_init`] return `NULL`, while those
+/// registered by [`sqlite3_uuid_init_strict`] raise an `invalid UUID argument`
+/// error instead. A `NULL` argument yields `NULL` in both modes.
+///
+/// # Arguments
+/// * `ctx` - The SQLite function context.
+/// * `arg` - The argument which could not be parsed as a UUID.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `ctx` and `arg`
+/// pointers.
+unsafe fn result_invalid_uuid(ctx: *mut sqlite3_context, arg: *mut sqlite3_value) {
+    let strict = unsafe { registration(ctx) }.strict;
+    if strict && unsafe { sqlite3_value_type(arg) } != SQLITE_NULL {
+        unsafe {
+            sqlite3_result_error(ctx, c"invalid UUID argument".as_ptr(), -1);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
 // --- SQL Functions (UUIDv7) ---
 
 /// SQL Function: `uuid7()`
 ///
 /// Generates a UUIDv7 (time-ordered) and returns it as a canonical 36-character
-/// string.
+/// string. UUIDs generated within the same millisecond are strictly
+/// increasing, see [`generate::now_v7`].
 unsafe extern "C" fn uuid7_func(
     ctx: *mut sqlite3_context,
     _argc: c_int,
     _argv: *mut *mut sqlite3_value,
 ) {
-    let u = Uuid::now_v7();
-    let s = u.to_string(); // canonical 36-char string
-    let c_str = CString::new(s).unwrap();
     unsafe {
-        sqlite3_result_text(ctx, c_str.as_ptr(), -1, SQLITE_TRANSIENT());
+        result_uuid_text(ctx, &generate::now_v7());
     }
 }
 
 /// SQL Function: `uuid7_blob()`
+///
+/// Without arguments, generates a UUIDv7 from the same monotonic source as
+/// `uuid7()` and returns it as a 16-byte BLOB.
 unsafe extern "C" fn uuid7_blob_func(
     ctx: *mut sqlite3_context,
     argc: c_int,
     argv: *mut *mut sqlite3_value,
 ) {
     if argc == 0 {
-        let u = Uuid::now_v7();
-        let bytes = u.as_bytes();
         unsafe {
-            sqlite3_result_blob(ctx, bytes.as_ptr().cast::<c_void>(), 16, SQLITE_TRANSIENT());
+            result_uuid_blob(ctx, &generate::now_v7());
         }
         return;
     }
 
     if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
-        let bytes = u.as_bytes();
         unsafe {
-            sqlite3_result_blob(ctx, bytes.as_ptr().cast::<c_void>(), 16, SQLITE_TRANSIENT());
+            result_uuid_blob(ctx, &u);
+        }
+    } else {
+        unsafe {
+            result_invalid_uuid(ctx, *argv);
+        }
+    }
+}
+
+/// Implementation of the `uuid_generate_monotonic_text()` SQL function.
+///
+/// Returns a UUIDv7 as a canonical 36-character string that is strictly
+/// lexicographically greater than any previously returned by this function
+/// within the same process, even when generated within the same millisecond.
+unsafe extern "C" fn uuid_generate_monotonic_text_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        result_uuid_text(ctx, &generate::now_v7());
+    }
+}
+
+/// Largest batch size accepted by `uuid_now_v7_text_batch`.
+const MAX_V7_BATCH: i64 = 10_000;
+
+/// Implementation of the `uuid_now_v7_text_batch(n)` SQL function.
+///
+/// Returns a JSON array of `n` UUIDv7s as canonical 36-character strings,
+/// drawn from the monotonic source of [`generate::now_v7`] and hence
+/// strictly increasing, or `NULL` if `n` is not an INTEGER between 0 and
+/// 10000.
+unsafe extern "C" fn uuid_now_v7_text_batch_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let Some(n) = unsafe { value_int64(*argv) }.filter(|n| (0..=MAX_V7_BATCH).contains(n)) else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+        return;
+    };
+    let n = usize::try_from(n).unwrap();
+    let mut batch = String::with_capacity(2 + n * 39);
+    batch.push('[');
+    for i in 0..n {
+        if i > 0 {
+            batch.push(',');
+        }
+        write!(batch, "\"{}\"", generate::now_v7()).unwrap();
+    }
+    batch.push(']');
+    unsafe {
+        result_text(ctx, batch);
+    }
+}
+
+/// Implementation of the `uuid7_timestamp(X)` SQL function.
+///
+/// Returns the 48-bit timestamp embedded in the UUIDv7 X as an INTEGER number
+/// of milliseconds since the Unix epoch, or `NULL` if X is not a valid UUIDv7.
+unsafe extern "C" fn uuid7_timestamp_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let timestamp = unsafe { parse_uuid_arg(argv, 0) }
+        .filter(|u| u.get_version_num() == 7)
+        .and_then(|u| u.get_timestamp());
+    if let Some(timestamp) = timestamp {
+        let (seconds, nanos) = timestamp.to_unix();
+        let millis = seconds * 1000 + u64::from(nanos / 1_000_000);
+        unsafe {
+            sqlite3_result_int64(ctx, i64::try_from(millis).unwrap());
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Formats a Unix timestamp as an SQLite-compatible UTC datetime string.
+///
+/// # Arguments
+/// * `seconds` - The number of seconds since the Unix epoch.
+/// * `millis` - The milliseconds within the second.
+///
+/// # Returns
+/// * `String` - The datetime as `YYYY-MM-DD HH:MM:SS.SSS`.
+fn format_datetime(seconds: u64, millis: u32) -> String {
+    let days = seconds / 86_400;
+    let time = seconds % 86_400;
+    // Converts the days since the epoch to a proleptic Gregorian date, from
+    // Howard Hinnant's `civil_from_days`, shifted to start on 0000-03-01.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}.{millis:03}",
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+/// Implementation of the `uuid7_datetime(X)` SQL function.
+///
+/// Returns the timestamp embedded in the UUIDv7 X as a UTC datetime string
+/// `YYYY-MM-DD HH:MM:SS.SSS`, as understood by SQLite's date and time
+/// functions, or `NULL` if X is not a valid UUIDv7.
+unsafe extern "C" fn uuid7_datetime_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let timestamp = unsafe { parse_uuid_arg(argv, 0) }
+        .filter(|u| u.get_version_num() == 7)
+        .and_then(|u| u.get_timestamp());
+    if let Some(timestamp) = timestamp {
+        let (seconds, nanos) = timestamp.to_unix();
+        unsafe {
+            result_text(ctx, format_datetime(seconds, nanos / 1_000_000));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Helper function to build a UUIDv7 from the millisecond timestamp argument
+/// of the `uuid7_at` functions.
+///
+/// # Arguments
+/// * `argv` - Pointer to the array of sqlite3_value pointers.
+///
+/// # Returns
+/// * `Option<Uuid>` - The UUIDv7 embedding the timestamp, with random
+///   remaining bits, or `None` if the argument is not an INTEGER between 0
+///   and 2^48 - 1.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers from `argv`,
+/// which must hold at least one element.
+unsafe fn v7_at(argv: *mut *mut sqlite3_value) -> Option<Uuid> {
+    unsafe { value_int64(*argv) }.and_then(generate::v7_at_millis)
+}
+
+/// Implementation of the `uuid7_at(ms)` SQL function.
+///
+/// Returns a UUIDv7 embedding the given millisecond Unix timestamp instead of
+/// the current time as a canonical 36-character string, or `NULL` if `ms` is
+/// not an INTEGER between 0 and 2^48 - 1.
+unsafe extern "C" fn uuid7_at_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { v7_at(argv) } {
+        unsafe {
+            result_uuid_text(ctx, &u);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid7_at_blob(ms)` SQL function.
+///
+/// Same as `uuid7_at`, but returns the UUID as a 16-byte BLOB.
+unsafe extern "C" fn uuid7_at_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { v7_at(argv) } {
+        unsafe {
+            result_uuid_blob(ctx, &u);
         }
     } else {
         unsafe {
@@ -119,30 +528,58 @@ unsafe extern "C" fn uuid_func(
     _argc: c_int,
     _argv: *mut *mut sqlite3_value,
 ) {
-    let u = Uuid::new_v4();
-    let s = u.to_string();
-    let c_str = CString::new(s).unwrap();
     unsafe {
-        sqlite3_result_text(ctx, c_str.as_ptr(), -1, SQLITE_TRANSIENT());
+        result_uuid_text(ctx, &generate::new_v4());
+    }
+}
+
+/// Formats a UUID according to a `format` argument of `uuid_str`.
+///
+/// # Arguments
+/// * `u` - The UUID to format.
+/// * `format` - One of `hyphenated`, `simple`, `braced`, `upper` (uppercase
+///   hyphenated) or `simple_upper` (32 uppercase hex digits).
+///
+/// # Returns
+/// * `Option<String>` - The formatted UUID, or `None` if the format is
+///   unknown.
+fn format_uuid(u: &Uuid, format: &str) -> Option<String> {
+    match format {
+        "hyphenated" => Some(u.hyphenated().to_string()),
+        "simple" => Some(u.simple().to_string()),
+        "braced" => Some(u.braced().to_string()),
+        "upper" => Some(format!("{:X}", u.hyphenated())),
+        "simple_upper" => Some(format!("{:X}", u.simple())),
+        _ => None,
     }
 }
 
-/// Implementation of the `uuid_str(X)` SQL function.
+/// Implementation of the `uuid_str(X)` and `uuid_str(X, format)` SQL
+/// functions.
+///
+/// Returns the UUID X as a canonical 36-character string or, when a `format`
+/// is given, in the form it selects (see [`format_uuid`]). Returns `NULL` if
+/// X is not a valid UUID or the format is unknown.
 unsafe extern "C" fn uuid_str_func(
     ctx: *mut sqlite3_context,
-    _argc: c_int,
+    argc: c_int,
     argv: *mut *mut sqlite3_value,
 ) {
-    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
-        let s = u.to_string();
-        let c_str = CString::new(s).unwrap();
+    let Some(u) = (unsafe { parse_uuid_arg(argv, 0) }) else {
         unsafe {
-            sqlite3_result_text(ctx, c_str.as_ptr(), -1, SQLITE_TRANSIENT());
+            result_invalid_uuid(ctx, *argv);
         }
-    } else {
+        return;
+    };
+    if argc == 1 {
         unsafe {
-            sqlite3_result_null(ctx);
+            result_uuid_text(ctx, &u);
         }
+        return;
+    }
+    match unsafe { value_text(*argv.add(1)) }.and_then(|format| format_uuid(&u, format)) {
+        Some(s) => unsafe { result_text(ctx, s) },
+        None => unsafe { sqlite3_result_null(ctx) },
     }
 }
 
@@ -153,184 +590,2494 @@ unsafe extern "C" fn uuid_blob_func(
     argv: *mut *mut sqlite3_value,
 ) {
     if argc == 0 {
-        let u = Uuid::new_v4();
-        let bytes = u.as_bytes();
         unsafe {
-            sqlite3_result_blob(ctx, bytes.as_ptr().cast::<c_void>(), 16, SQLITE_TRANSIENT());
+            result_uuid_blob(ctx, &generate::new_v4());
         }
         return;
     }
 
     if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
-        let bytes = u.as_bytes();
         unsafe {
-            sqlite3_result_blob(ctx, bytes.as_ptr().cast::<c_void>(), 16, SQLITE_TRANSIENT());
+            result_uuid_blob(ctx, &u);
         }
     } else {
         unsafe {
-            sqlite3_result_null(ctx);
+            result_invalid_uuid(ctx, *argv);
         }
     }
 }
 
-// --- Extension Entry Point ---
+// --- SQL Functions (UUIDv1) ---
 
-/// SQLite Extension Entry Point: `sqlite3_uuid_init`
-///
-/// Registers the following SQL functions with the SQLite database connection:
-/// - `uuid`
-/// - `uuid_str`
-/// - `uuid_blob`
-/// - `uuid7`
-/// - `uuid7_blob`
+/// Largest node accepted by `uuid_v1_with_clock_seq`, which is 48 bits wide.
+const MAX_NODE: i64 = 0xFFFF_FFFF_FFFF;
+/// Largest clock sequence accepted by `uuid_v1_with_clock_seq`, which is 14
+/// bits wide.
+const MAX_CLOCK_SEQ: i64 = 0x3FFF;
+
+/// Helper function to build a UUIDv1 at the current time from the
+/// `(node, clock_seq)` arguments of the `uuid_v1_with_clock_seq` functions.
 ///
 /// # Arguments
-/// * `db` - The SQLite database connection.
-/// * `_pz_err_msg` - Pointer to error message pointer (unused).
-/// * `_p_api` - Pointer to SQLite API (unused, assuming linked implementation).
+/// * `argv` - Pointer to the array of sqlite3_value pointers.
 ///
 /// # Returns
-/// * `SQLITE_OK` on success, or an error code.
+/// * `Option<Uuid>` - The UUIDv1, or `None` if either argument is not an
+///   INTEGER or is out of range (48 bits for the node, 14 bits for the clock
+///   sequence).
 ///
 /// # Safety
-/// This function is unsafe because it interacts with raw SQLite pointers.
-/// It assumes `db` is a valid SQLite database connection.
-#[unsafe(no_mangle)]
-#[allow(clippy::too_many_lines)]
-pub unsafe extern "C" fn sqlite3_uuid_init(
-    db: *mut sqlite3,
-    _pz_err_msg: *mut *mut c_char,
-    _p_api: *const sqlite3_api_routines,
-) -> c_int {
-    let flags = SQLITE_UTF8 | SQLITE_INNOCUOUS;
-    let deterministic = flags | SQLITE_DETERMINISTIC;
-
-    // --- UUIDv7 Registration ---
+/// This function is unsafe because it dereferences raw pointers from `argv`,
+/// which must hold at least two elements.
+unsafe fn v1_with_clock_seq(argv: *mut *mut sqlite3_value) -> Option<Uuid> {
+    let node = unsafe { value_int64(*argv) }.filter(|node| (0..=MAX_NODE).contains(node))?;
+    let clock_seq = unsafe { value_int64(*argv.add(1)) }
+        .filter(|clock_seq| (0..=MAX_CLOCK_SEQ).contains(clock_seq))?;
+    let node_id: [u8; 6] = node.to_be_bytes()[2..].try_into().unwrap();
+    let (seconds, subsec_nanos) = Timestamp::now(NoContext).to_unix();
+    let clock_seq = u128::try_from(clock_seq).unwrap();
+    // A UUIDv1 holds a 14-bit clock sequence.
+    let timestamp = Timestamp::from_unix_time(seconds, subsec_nanos, clock_seq, 14);
+    Some(Uuid::new_v1(timestamp, &node_id))
+}
 
-    let rc = unsafe {
-        sqlite3_create_function_v2(
-            db,
-            c"uuid7".as_ptr(),
-            0,
-            flags,
-            ptr::null_mut(),
-            Some(uuid7_func),
-            None,
-            None,
-            None,
-        )
-    };
-    if rc != SQLITE_OK {
-        return rc;
+/// Implementation of the `uuid_v1_with_clock_seq(node, clock_seq)` SQL
+/// function.
+///
+/// Returns a UUIDv1 for the current time with the given 48-bit `node` and
+/// 14-bit `clock_seq` as a canonical 36-character string, or `NULL` if either
+/// is not an INTEGER in range. Meant to reproduce specific UUIDv1s in tests.
+unsafe extern "C" fn uuid_v1_with_clock_seq_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { v1_with_clock_seq(argv) } {
+        unsafe {
+            result_uuid_text(ctx, &u);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
     }
+}
 
-    let rc = unsafe {
-        sqlite3_create_function_v2(
-            db,
-            c"uuid7_blob".as_ptr(),
-            0,
-            flags,
-            ptr::null_mut(),
-            Some(uuid7_blob_func),
-            None,
-            None,
-            None,
-        )
-    };
-    if rc != SQLITE_OK {
-        return rc;
+/// Implementation of the `uuid_v1_with_clock_seq_blob(node, clock_seq)` SQL
+/// function.
+///
+/// Same as `uuid_v1_with_clock_seq`, but returns the UUID as a 16-byte BLOB.
+unsafe extern "C" fn uuid_v1_with_clock_seq_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { v1_with_clock_seq(argv) } {
+        unsafe {
+            result_uuid_blob(ctx, &u);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid_mask_node(X)` SQL function.
+///
+/// Returns the UUID X as a canonical 36-character string, with the 48-bit
+/// node of UUIDv1s and UUIDv6s, which may hold the MAC address of the
+/// generating host, set to zero. The timestamp, clock sequence, version and
+/// variant are preserved, and UUIDs of other versions are returned unchanged.
+/// Returns `NULL` if X is not a valid UUID.
+unsafe extern "C" fn uuid_mask_node_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
+        let mut bytes = u.into_bytes();
+        if matches!(u.get_version_num(), 1 | 6) {
+            bytes[10..].fill(0);
+        }
+        unsafe {
+            result_uuid_text(ctx, &Uuid::from_bytes(bytes));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+// --- SQL Functions (Name-based) ---
+
+/// Helper function to parse the `(namespace, name)` arguments of the
+/// name-based SQL functions.
+///
+/// # Arguments
+/// * `argv` - Pointer to the array of sqlite3_value pointers.
+///
+/// # Returns
+/// * `Option<(Uuid, &str)>` - The namespace UUID and the UTF-8 name, or `None`
+///   if the namespace is not a valid UUID or the name is `NULL`.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers from `argv`,
+/// which must hold at least two elements.
+unsafe fn parse_name_based_args<'a>(argv: *mut *mut sqlite3_value) -> Option<(Uuid, &'a str)> {
+    let namespace = unsafe { parse_uuid_arg(argv, 0) }?;
+    let name = unsafe { value_text(*argv.add(1)) }?;
+    Some((namespace, name))
+}
+
+/// Implementation of the `uuid3(namespace, name)` SQL function.
+///
+/// Returns the UUIDv3 (MD5, name-based) of `name` in `namespace` as a
+/// canonical 36-character string, or `NULL` if `namespace` is not a valid
+/// UUID.
+unsafe extern "C" fn uuid3_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some((namespace, name)) = unsafe { parse_name_based_args(argv) } {
+        unsafe {
+            result_uuid_text(ctx, &Uuid::new_v3(&namespace, name.as_bytes()));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid3_blob(namespace, name)` SQL function.
+///
+/// Same as `uuid3`, but returns the UUID as a 16-byte BLOB.
+unsafe extern "C" fn uuid3_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some((namespace, name)) = unsafe { parse_name_based_args(argv) } {
+        unsafe {
+            result_uuid_blob(ctx, &Uuid::new_v3(&namespace, name.as_bytes()));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid5(namespace, name)` SQL function.
+///
+/// Returns the UUIDv5 (SHA-1, name-based) of `name` in `namespace` as a
+/// canonical 36-character string, or `NULL` if `namespace` is not a valid
+/// UUID.
+unsafe extern "C" fn uuid5_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some((namespace, name)) = unsafe { parse_name_based_args(argv) } {
+        unsafe {
+            result_uuid_text(ctx, &Uuid::new_v5(&namespace, name.as_bytes()));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid5_blob(namespace, name)` SQL function.
+///
+/// Same as `uuid5`, but returns the UUID as a 16-byte BLOB.
+unsafe extern "C" fn uuid5_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some((namespace, name)) = unsafe { parse_name_based_args(argv) } {
+        unsafe {
+            result_uuid_blob(ctx, &Uuid::new_v5(&namespace, name.as_bytes()));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid_ns_dns()` SQL function.
+///
+/// Returns the well-known RFC 9562 namespace UUID for domain names as a
+/// canonical 36-character string. The `uuid_ns_*` functions spare callers
+/// from spelling out these constants when passing them to `uuid3` and `uuid5`.
+unsafe extern "C" fn uuid_ns_dns_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        result_uuid_text(ctx, &Uuid::NAMESPACE_DNS);
+    }
+}
+
+/// Implementation of the `uuid_ns_url()` SQL function.
+///
+/// Same as `uuid_ns_dns`, for URLs.
+unsafe extern "C" fn uuid_ns_url_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        result_uuid_text(ctx, &Uuid::NAMESPACE_URL);
+    }
+}
+
+/// Implementation of the `uuid_ns_oid()` SQL function.
+///
+/// Same as `uuid_ns_dns`, for ISO object identifiers.
+unsafe extern "C" fn uuid_ns_oid_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        result_uuid_text(ctx, &Uuid::NAMESPACE_OID);
+    }
+}
+
+/// Implementation of the `uuid_ns_x500()` SQL function.
+///
+/// Same as `uuid_ns_dns`, for X.500 distinguished names.
+unsafe extern "C" fn uuid_ns_x500_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        result_uuid_text(ctx, &Uuid::NAMESPACE_X500);
+    }
+}
+
+// --- SQL Functions (Sentinels) ---
+
+/// Implementation of the `uuid_nil()` SQL function.
+///
+/// Returns the nil UUID, with all bits set to zero, as a canonical
+/// 36-character string.
+unsafe extern "C" fn uuid_nil_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        result_uuid_text(ctx, &Uuid::nil());
+    }
+}
+
+/// Implementation of the `uuid_nil_blob()` SQL function.
+///
+/// Same as `uuid_nil`, but returns the UUID as a 16-byte BLOB.
+unsafe extern "C" fn uuid_nil_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        result_uuid_blob(ctx, &Uuid::nil());
+    }
+}
+
+/// Implementation of the `uuid_max()` SQL function.
+///
+/// Returns the max UUID, with all bits set to one, as a canonical
+/// 36-character string.
+unsafe extern "C" fn uuid_max_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        result_uuid_text(ctx, &Uuid::max());
+    }
+}
+
+/// Implementation of the `uuid_max_blob()` SQL function.
+///
+/// Same as `uuid_max`, but returns the UUID as a 16-byte BLOB.
+unsafe extern "C" fn uuid_max_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        result_uuid_blob(ctx, &Uuid::max());
+    }
+}
+
+// --- SQL Functions (Inspection) ---
+
+/// Implementation of the `uuid_version(X)` SQL function.
+///
+/// Returns the version number of the UUID X (e.g. 4 or 7) as an INTEGER, or
+/// `NULL` if X is not a valid UUID.
+unsafe extern "C" fn uuid_version_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
+        let version = c_int::try_from(u.get_version_num()).unwrap();
+        unsafe {
+            sqlite3_result_int(ctx, version);
+        }
+    } else {
+        unsafe {
+            result_invalid_uuid(ctx, *argv);
+        }
+    }
+}
+
+/// Implementation of the `uuid_version_of_blob(X)` SQL function.
+///
+/// Returns the high nibble of the 7th byte of a 16-byte BLOB, which holds the
+/// version of a UUID, as an INTEGER between 0 and 15, without otherwise
+/// validating the UUID. Returns `NULL` if X is not a 16-byte BLOB. This is a
+/// cheaper alternative to `uuid_version` for BLOB columns.
+unsafe extern "C" fn uuid_version_of_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    match unsafe { value_blob(*argv) } {
+        Some(bytes) if bytes.len() == 16 => unsafe {
+            sqlite3_result_int(ctx, c_int::from(bytes[6] >> 4));
+        },
+        _ => unsafe {
+            sqlite3_result_null(ctx);
+        },
+    }
+}
+
+/// Implementation of the `uuid_variant(X)` SQL function.
+///
+/// Returns the variant of the UUID X as a TEXT: `'rfc4122'` for the layout
+/// defined by RFC 4122 and RFC 9562, `'microsoft'` for legacy Microsoft GUIDs,
+/// `'ncs'` for the NCS backward-compatible layout and `'future'` for the
+/// reserved one. Returns `NULL` if X is not a valid UUID.
+unsafe extern "C" fn uuid_variant_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let variant = unsafe { parse_uuid_arg(argv, 0) }.map(|u| match u.get_variant() {
+        uuid::Variant::NCS => "ncs",
+        uuid::Variant::RFC4122 => "rfc4122",
+        uuid::Variant::Microsoft => "microsoft",
+        _ => "future",
+    });
+    match variant {
+        Some(variant) => unsafe { result_text(ctx, variant.to_string()) },
+        None => unsafe { result_invalid_uuid(ctx, *argv) },
+    }
+}
+
+/// Implementation of the `uuid_is_valid(X)` SQL function.
+///
+/// Returns `1` if X is a valid UUID, in TEXT or BLOB form, and `0` otherwise,
+/// including when X is `NULL`, so that it can be used directly in a `WHERE`
+/// clause.
+unsafe extern "C" fn uuid_is_valid_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let valid = unsafe { parse_uuid_arg(argv, 0) }.is_some();
+    unsafe {
+        sqlite3_result_int(ctx, c_int::from(valid));
+    }
+}
+
+/// Implementation of the `uuid_timestamp_resolution(X)` SQL function.
+///
+/// Returns the resolution of the timestamp embedded in the UUID X as a TEXT:
+/// `'100ns'` for UUIDv1 and UUIDv6, which count 100-nanosecond intervals, and
+/// `'1ms'` for UUIDv7, which counts milliseconds. Returns `NULL` if X is not a
+/// valid UUID or its version carries no timestamp.
+unsafe extern "C" fn uuid_timestamp_resolution_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let resolution = unsafe { parse_uuid_arg(argv, 0) }.and_then(|u| match u.get_version_num() {
+        1 | 6 => Some("100ns"),
+        7 => Some("1ms"),
+        _ => None,
+    });
+    match resolution {
+        Some(resolution) => unsafe { result_text(ctx, resolution.to_string()) },
+        None => unsafe { sqlite3_result_null(ctx) },
+    }
+}
+
+/// Implementation of the `uuid_extract_timestamp(X)` SQL function.
+///
+/// Returns the timestamp embedded in the UUIDv1, UUIDv6 or UUIDv7 X as an
+/// INTEGER number of milliseconds since the Unix epoch, negative for UUIDv1
+/// and UUIDv6 from before 1970, flooring the sub-millisecond precision of
+/// those versions. Returns `NULL` if X is not a valid UUID of one of those
+/// versions.
+unsafe extern "C" fn uuid_extract_timestamp_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    match unsafe { parse_uuid_arg(argv, 0) }.and_then(|u| timestamp_millis(&u)) {
+        Some(millis) => unsafe {
+            sqlite3_result_int64(ctx, millis);
+        },
+        None => unsafe {
+            sqlite3_result_null(ctx);
+        },
+    }
+}
+
+/// Returns the timestamp embedded in a UUIDv1, UUIDv6 or UUIDv7 in
+/// milliseconds since the Unix epoch, or `None` for other versions.
+///
+/// The timestamp is read as 100ns ticks since the Gregorian epoch of 1582,
+/// which UUIDv1 and UUIDv6 can predate the Unix epoch by, and which
+/// [`Timestamp::to_unix`] would wrap around.
+fn timestamp_millis(u: &Uuid) -> Option<i64> {
+    if !matches!(u.get_version_num(), 1 | 6 | 7) {
+        return None;
+    }
+    let (ticks, _) = u.get_timestamp()?.to_gregorian();
+    let ticks = i64::try_from(ticks).ok()?;
+    let unix_ticks = ticks.checked_sub(i64::try_from(UUID_TICKS_BETWEEN_EPOCHS).ok()?)?;
+    Some(unix_ticks.div_euclid(10_000))
+}
+
+/// Implementation of the `uuid_clock_seq(X)` SQL function.
+///
+/// Returns the 14-bit clock sequence of the UUIDv1 or UUIDv6 X as an INTEGER,
+/// or `NULL` if X is not a valid UUID of one of those versions.
+unsafe extern "C" fn uuid_clock_seq_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    match unsafe { parse_uuid_arg(argv, 0) } {
+        Some(u) if matches!(u.get_version_num(), 1 | 6) => {
+            let bytes = u.as_bytes();
+            let clock_seq = u16::from_be_bytes([bytes[8], bytes[9]]) & 0x3FFF;
+            unsafe {
+                sqlite3_result_int(ctx, c_int::from(clock_seq));
+            }
+        }
+        _ => unsafe {
+            sqlite3_result_null(ctx);
+        },
+    }
+}
+
+/// Implementation of the `uuid_node(X)` SQL function.
+///
+/// Returns the 48-bit node of the UUIDv1 or UUIDv6 X as an INTEGER, or `NULL`
+/// if X is not a valid UUID of one of those versions.
+unsafe extern "C" fn uuid_node_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    match unsafe { parse_uuid_arg(argv, 0) }.and_then(|u| u.get_node_id()) {
+        Some(node_id) => {
+            let mut bytes = [0u8; 8];
+            bytes[2..].copy_from_slice(&node_id);
+            unsafe {
+                sqlite3_result_int64(ctx, i64::from_be_bytes(bytes));
+            }
+        }
+        None => unsafe {
+            sqlite3_result_null(ctx);
+        },
+    }
+}
+
+// --- SQL Functions (Comparison) ---
+
+/// Implementation of the `uuid_compare(A, B)` SQL function.
+///
+/// Compares the 16-byte representations of A and B, each given as TEXT or
+/// BLOB, returning -1, 0 or 1 as an INTEGER, or `NULL` if either is not a
+/// valid UUID.
+unsafe extern "C" fn uuid_compare_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let a = unsafe { parse_uuid_arg(argv, 0) };
+    let b = unsafe { parse_uuid_arg(argv, 1) };
+    if let (Some(a), Some(b)) = (a, b) {
+        let ordering = a.as_bytes().cmp(b.as_bytes());
+        unsafe {
+            sqlite3_result_int(ctx, c_int::from(ordering as i8));
+        }
+    } else {
+        // A malformed argument is reported even next to a `NULL` one.
+        let args = unsafe { [*argv, *argv.add(1)] };
+        let malformed = [a, b].into_iter().zip(args).find(|&(parsed, arg)| {
+            parsed.is_none() && unsafe { sqlite3_value_type(arg) } != SQLITE_NULL
+        });
+        match malformed {
+            Some((_, arg)) => unsafe { result_invalid_uuid(ctx, arg) },
+            None => unsafe { sqlite3_result_null(ctx) },
+        }
+    }
+}
+
+/// Implementation of the `uuid_compare_version(A, B)` SQL function.
+///
+/// When both A and B are timestamped UUIDs (UUIDv1, UUIDv6 or UUIDv7),
+/// compares their embedded timestamps at millisecond precision, so that UUIDs
+/// of different versions created at the same instant compare equal. Otherwise
+/// compares their 16 bytes. Returns -1, 0 or 1 as an INTEGER, or `NULL` if
+/// either is not a valid UUID.
+unsafe extern "C" fn uuid_compare_version_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let a = unsafe { parse_uuid_arg(argv, 0) };
+    let b = unsafe { parse_uuid_arg(argv, 1) };
+    if let (Some(a), Some(b)) = (a, b) {
+        let ordering = match (timestamp_millis(&a), timestamp_millis(&b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => a.cmp(&b),
+        };
+        unsafe {
+            sqlite3_result_int(ctx, c_int::from(ordering as i8));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid_eq(A, B)` SQL function.
+///
+/// Returns `1` if A and B, each given as TEXT or BLOB, are the same UUID and
+/// `0` otherwise, as an INTEGER, or `NULL` if either is not a valid UUID.
+/// Unlike `=`, a TEXT and a BLOB of the same UUID compare equal, so that
+/// e.g. `ON uuid_eq(users.id, posts.author_id)` joins a TEXT column against a
+/// BLOB one.
+unsafe extern "C" fn uuid_eq_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let a = unsafe { parse_uuid_arg(argv, 0) };
+    let b = unsafe { parse_uuid_arg(argv, 1) };
+    if let (Some(a), Some(b)) = (a, b) {
+        unsafe {
+            sqlite3_result_int(ctx, c_int::from(a == b));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+// --- SQL Functions (Aggregates) ---
+
+/// State of the `uuid7_min` and `uuid7_max` aggregates for a group of rows.
+///
+/// The state is allocated by `sqlite3_aggregate_context`, which zeroes it, so
+/// that a fresh state has seen no UUID.
+#[repr(C)]
+struct ExtremumState {
+    /// Whether a UUID was seen in the group.
+    seen: bool,
+    /// Whether the current extremum was provided as a BLOB.
+    blob: bool,
+    /// Bytes of the current extremum.
+    bytes: [u8; 16],
+}
+
+/// Shared `xStep` callback of the `uuid7_min` and `uuid7_max` aggregates.
+///
+/// Parses the argument as a UUID and keeps it if the group is empty so far or
+/// if it compares to the current extremum as `replace_if`. `NULL` arguments
+/// are skipped, as are invalid ones unless registered in strict mode, where
+/// they raise an `invalid UUID argument` error.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw pointers provided
+/// by SQLite to an `xStep` callback.
+unsafe fn extremum_step(
+    ctx: *mut sqlite3_context,
+    argv: *mut *mut sqlite3_value,
+    replace_if: Ordering,
+) {
+    let arg = unsafe { *argv };
+    let parsed = unsafe { parse_uuid_arg(argv, 0) };
+    let Some(u) = parsed else {
+        let strict = unsafe { registration(ctx) }.strict;
+        if strict && unsafe { sqlite3_value_type(arg) } != SQLITE_NULL {
+            unsafe {
+                sqlite3_result_error(ctx, c"invalid UUID argument".as_ptr(), -1);
+            }
+        }
+        return;
+    };
+    let size = c_int::try_from(size_of::<ExtremumState>()).unwrap();
+    let state = unsafe { sqlite3_aggregate_context(ctx, size) }.cast::<ExtremumState>();
+    if state.is_null() {
+        unsafe {
+            sqlite3_result_error_nomem(ctx);
+        }
+        return;
+    }
+    let state = unsafe { &mut *state };
+    if !state.seen || u.as_bytes().cmp(&state.bytes) == replace_if {
+        state.seen = true;
+        state.blob = unsafe { sqlite3_value_type(arg) } == SQLITE_BLOB;
+        state.bytes = u.into_bytes();
+    }
+}
+
+/// Shared `xFinal` callback of the `uuid7_min` and `uuid7_max` aggregates.
+///
+/// Returns the extremum of the group in the form it was provided in: a 16-byte
+/// BLOB for a BLOB, a canonical 36-character string for a TEXT. Returns `NULL`
+/// if the group held no valid UUID.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `ctx` pointer.
+unsafe extern "C" fn extremum_final(ctx: *mut sqlite3_context) {
+    // A size of 0 does not allocate the state if no row was ever stepped.
+    let state = unsafe { sqlite3_aggregate_context(ctx, 0) }.cast::<ExtremumState>();
+    match unsafe { state.as_ref() } {
+        Some(state) if state.seen => {
+            let u = Uuid::from_bytes(state.bytes);
+            if state.blob {
+                unsafe { result_uuid_blob(ctx, &u) }
+            } else {
+                unsafe { result_uuid_text(ctx, &u) }
+            }
+        }
+        _ => unsafe { sqlite3_result_null(ctx) },
+    }
+}
+
+/// `xStep` callback of the `uuid7_min(X)` aggregate SQL function.
+///
+/// Returns the smallest UUID of the group by 16-byte order, which is the
+/// oldest for UUIDv7, whatever their case or hyphenation, see
+/// [`extremum_step`] and [`extremum_final`].
+unsafe extern "C" fn uuid7_min_step(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    unsafe { extremum_step(ctx, argv, Ordering::Less) }
+}
+
+/// `xStep` callback of the `uuid7_max(X)` aggregate SQL function.
+///
+/// Returns the largest UUID of the group by 16-byte order, which is the
+/// latest for UUIDv7, whatever their case or hyphenation, see
+/// [`extremum_step`] and [`extremum_final`].
+unsafe extern "C" fn uuid7_max_step(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    unsafe { extremum_step(ctx, argv, Ordering::Greater) }
+}
+
+// --- SQL Functions (Integer Blobs) ---
+
+/// Implementation of the `uuid_as_integer_blob(X)` SQL function.
+///
+/// Returns the UUID X as a 16-byte big-endian integer BLOB, which is the same
+/// layout as `uuid_blob(X)`, or `NULL` if X is not a valid UUID.
+unsafe extern "C" fn uuid_as_integer_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
+        unsafe {
+            result_uuid_blob(ctx, &u);
+        }
+    } else {
+        unsafe {
+            result_invalid_uuid(ctx, *argv);
+        }
+    }
+}
+
+/// Implementation of the `uuid_from_integer_blob(X)` SQL function.
+///
+/// Converts a big-endian integer BLOB of at most 16 bytes, such as those
+/// produced by arbitrary-precision integer columns stripping leading zeros,
+/// into a 16-byte UUID BLOB by left-padding it with zeros. Returns `NULL` if
+/// X is not a BLOB or is longer than 16 bytes.
+unsafe extern "C" fn uuid_from_integer_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    match unsafe { value_blob(*argv) } {
+        Some(integer) if integer.len() <= 16 => {
+            let mut bytes = [0u8; 16];
+            bytes[16 - integer.len()..].copy_from_slice(integer);
+            unsafe {
+                result_uuid_blob(ctx, &Uuid::from_bytes(bytes));
+            }
+        }
+        _ => unsafe {
+            sqlite3_result_null(ctx);
+        },
+    }
+}
+
+// --- SQL Functions (GUID Byte Order) ---
+
+/// Implementation of the `uuid_to_guid(X)` SQL function.
+///
+/// Returns the UUID X, given as TEXT or as a 16-byte BLOB in network order,
+/// as a 16-byte BLOB in the mixed-endian memory layout of Microsoft GUIDs,
+/// where the first three fields (4, 2 and 2 bytes) are little-endian. Returns
+/// `NULL` if X is not a valid UUID.
+unsafe extern "C" fn uuid_to_guid_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
+        unsafe {
+            result_uuid_blob(ctx, &Uuid::from_bytes(u.to_bytes_le()));
+        }
+    } else {
+        unsafe {
+            result_invalid_uuid(ctx, *argv);
+        }
+    }
+}
+
+/// Implementation of the `uuid_guid_to_uuid(X)` SQL function.
+///
+/// Converts a 16-byte BLOB in the mixed-endian memory layout of Microsoft
+/// GUIDs, as serialized by .NET's `Guid.ToByteArray`, to the 16-byte BLOB in
+/// network order produced by `uuid_blob`. This is the inverse of
+/// `uuid_to_guid`. Returns `NULL` if X is not a 16-byte BLOB.
+unsafe extern "C" fn uuid_guid_to_uuid_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    match unsafe { value_blob(*argv) }.and_then(|bytes| <[u8; 16]>::try_from(bytes).ok()) {
+        Some(bytes) => unsafe {
+            result_uuid_blob(ctx, &Uuid::from_bytes_le(bytes));
+        },
+        None => unsafe {
+            sqlite3_result_null(ctx);
+        },
+    }
+}
+
+// --- SQL Functions (Relaxed Parsing) ---
+
+/// Recovers the 16 bytes of a UUID from a possibly length-prefixed BLOB.
+///
+/// A 16-byte BLOB is used as is. A 17, 18 or 20-byte BLOB is accepted if its
+/// leading 1, 2 or 4 bytes encode the length 16 as a big-endian or
+/// little-endian unsigned integer, as written by serializers that prefix
+/// byte strings with their length, and the prefix is stripped. Any other
+/// BLOB, including a 19-byte one, is rejected.
+fn parse_relaxed_blob(bytes: &[u8]) -> Option<Uuid> {
+    let (prefix, payload) = bytes.split_at_checked(bytes.len().checked_sub(16)?)?;
+    let length_prefixed = match prefix.len() {
+        0 => true,
+        1 | 2 | 4 => {
+            let big_endian = prefix.iter().fold(0u32, |value, &byte| value << 8 | u32::from(byte));
+            let little_endian =
+                prefix.iter().rev().fold(0u32, |value, &byte| value << 8 | u32::from(byte));
+            big_endian == 16 || little_endian == 16
+        }
+        _ => false,
+    };
+    length_prefixed.then(|| Uuid::from_slice(payload).ok()).flatten()
+}
+
+/// Implementation of the `uuid_parse_relaxed_blob(X)` SQL function.
+///
+/// Returns the UUID held by the BLOB X as a 16-byte BLOB, additionally
+/// accepting BLOBs of 17, 18 or 20 bytes starting with a 1, 2 or 4-byte
+/// length prefix equal to 16. Returns `NULL` if X is not a BLOB or no UUID
+/// can be recovered from it. All other functions keep requiring exactly 16
+/// bytes.
+unsafe extern "C" fn uuid_parse_relaxed_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { value_blob(*argv) }.and_then(parse_relaxed_blob) {
+        unsafe {
+            result_uuid_blob(ctx, &u);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+// --- SQL Functions (Pairs) ---
+
+/// Namespace of the UUIDv5 returned by `uuid_dedup_pair_key`, itself the
+/// UUIDv5 of `https://github.com/LucaCappelletti94/sqlite-wasm-uuid-rs#pair`
+/// in the URL namespace.
+const PAIR_NAMESPACE: Uuid = uuid::uuid!("d59d963c-09e1-5811-9fa6-54f37e456a85");
+
+/// Implementation of the `uuid_dedup_pair_key(A, B)` SQL function.
+///
+/// Returns a deterministic UUIDv5 identifying the unordered pair `{A, B}` as a
+/// canonical 36-character string: the two UUIDs are sorted, their bytes
+/// concatenated and hashed in a fixed namespace, so that `(A, B)` and `(B, A)`
+/// yield the same key. Returns `NULL` if either argument is not a valid UUID.
+unsafe extern "C" fn uuid_dedup_pair_key_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let a = unsafe { parse_uuid_arg(argv, 0) };
+    let b = unsafe { parse_uuid_arg(argv, 1) };
+    if let (Some(a), Some(b)) = (a, b) {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        let mut name = [0u8; 32];
+        name[..16].copy_from_slice(low.as_bytes());
+        name[16..].copy_from_slice(high.as_bytes());
+        unsafe {
+            result_uuid_text(ctx, &Uuid::new_v5(&PAIR_NAMESPACE, &name));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+// --- SQL Functions (Anonymization) ---
+
+/// Namespace of the hash computed by `uuid7_strip`, itself the UUIDv5 of
+/// `https://github.com/LucaCappelletti94/sqlite-wasm-uuid-rs#strip` in the URL
+/// namespace.
+const STRIP_NAMESPACE: Uuid = uuid::uuid!("8db3ec16-b49c-5fa0-8a7b-64f8a3711f73");
+
+/// Implementation of the `uuid7_strip(X)` SQL function.
+///
+/// Returns a UUIDv4 standing in for the UUID X as a canonical 36-character
+/// string, or `NULL` if X is not a valid UUID. The 122 free bits of the
+/// result are taken from the SHA-1 hash of the 16 bytes of X in a fixed
+/// namespace, so the result reveals nothing of the timestamp embedded in a
+/// UUIDv7, while the same input always maps to the same output: identifiers
+/// stay unique, and references between exported tables stay consistent.
+unsafe extern "C" fn uuid7_strip_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
+        let hash = Uuid::new_v5(&STRIP_NAMESPACE, u.as_bytes());
+        unsafe {
+            result_uuid_text(ctx, &uuid::Builder::from_random_bytes(hash.into_bytes()).into_uuid());
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+// --- SQL Functions (Partitioning) ---
+
+/// Largest number of prefix bits accepted by `uuid_hamming_bucket`, so that
+/// the bucket id always fits in the 53-bit safe integer range of JavaScript.
+const MAX_BUCKET_PREFIX_BITS: i64 = 52;
+
+/// Implementation of the `uuid_hamming_bucket(X, prefix_bits)` SQL function.
+///
+/// Returns the top `prefix_bits` bits of the UUID X as an INTEGER bucket id,
+/// so that UUIDs sharing a common prefix share a bucket. Returns `NULL` if X
+/// is not a valid UUID or `prefix_bits` is not an INTEGER between 1 and 52.
+unsafe extern "C" fn uuid_hamming_bucket_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let prefix_bits = unsafe { value_int64(*argv.add(1)) }
+        .filter(|bits| (1..=MAX_BUCKET_PREFIX_BITS).contains(bits));
+    if let (Some(u), Some(prefix_bits)) = (unsafe { parse_uuid_arg(argv, 0) }, prefix_bits) {
+        let bucket = u.as_u128() >> (128 - prefix_bits);
+        unsafe {
+            sqlite3_result_int64(ctx, i64::try_from(bucket).unwrap());
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid_shard(X, n)` SQL function.
+///
+/// Returns the shard, between 0 and `n - 1`, that the UUID X is routed to
+/// among `n` shards, computed as the remainder of the 128-bit big-endian
+/// value of X divided by `n`. Returns `NULL` if X is not a valid UUID or `n`
+/// is not a positive INTEGER.
+unsafe extern "C" fn uuid_shard_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let n_shards = unsafe { value_int64(*argv.add(1)) }.filter(|n| *n > 0);
+    if let (Some(u), Some(n_shards)) = (unsafe { parse_uuid_arg(argv, 0) }, n_shards) {
+        unsafe {
+            sqlite3_result_int64(ctx, uuid_shard(&u, n_shards));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Returns the shard the UUID is routed to among `n_shards` by `uuid_shard`.
+///
+/// # Arguments
+/// * `u` - The UUID to route.
+/// * `n_shards` - The positive number of shards.
+fn uuid_shard(u: &Uuid, n_shards: i64) -> i64 {
+    let n_shards = u128::try_from(n_shards).unwrap();
+    i64::try_from(u.as_u128() % n_shards).unwrap()
+}
+
+/// Maximum number of UUIDs drawn by `uuid_generate_for_shard` before giving
+/// up.
+const MAX_SHARD_ATTEMPTS: usize = 1 << 16;
+
+/// Implementation of the `uuid_generate_for_shard(total_shards, target_shard)`
+/// SQL function.
+///
+/// Draws random UUIDv4s until one is routed to `target_shard` among
+/// `total_shards` by `uuid_shard`, and returns it as a canonical 36-character
+/// string. Returns `NULL` if `target_shard` is not between 0 and
+/// `total_shards - 1`, or if no UUID was found within 65536 attempts, which
+/// only becomes likely with tens of thousands of shards.
+unsafe extern "C" fn uuid_generate_for_shard_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let total_shards = unsafe { value_int64(*argv) }.filter(|n| *n > 0);
+    let target_shard = unsafe { value_int64(*argv.add(1)) };
+    let found = match (total_shards, target_shard) {
+        (Some(total_shards), Some(target_shard)) if (0..total_shards).contains(&target_shard) => {
+            core::iter::repeat_with(generate::new_v4)
+                .take(MAX_SHARD_ATTEMPTS)
+                .find(|u| uuid_shard(u, total_shards) == target_shard)
+        }
+        _ => None,
+    };
+    if let Some(u) = found {
+        unsafe {
+            result_uuid_text(ctx, &u);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+// --- SQL Functions (Encodings) ---
+
+/// Base62 alphabet, made of URL-safe alphanumeric characters.
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Largest slug length accepted by `uuid_encode_url_path`, since 22 Base62
+/// characters already carry all the 128 bits of a UUID.
+const MAX_SLUG_LENGTH: i64 = 22;
+
+/// Encodes the leading bits of a UUID as a Base62 slug of the given length.
+///
+/// The UUID is read as a binary fraction in `[0, 1)` whose Base62 expansion
+/// is truncated to `length` digits, so that each character depends only on
+/// the bits before it and a shorter slug is always a prefix of a longer one.
+#[allow(clippy::cast_possible_truncation)]
+fn base62_slug(u: &Uuid, length: usize) -> String {
+    let (mut high, mut low) = u.as_u64_pair();
+    (0..length)
+        .map(|_| {
+            let low_product = u128::from(low) * 62;
+            let high_product = u128::from(high) * 62 + (low_product >> 64);
+            // Keep the fractional part of the product for the next digit.
+            low = low_product as u64;
+            high = high_product as u64;
+            char::from(BASE62[(high_product >> 64) as usize])
+        })
+        .collect()
+}
+
+/// Implementation of the `uuid_encode_url_path(X, length)` SQL function.
+///
+/// Returns a URL-safe slug of `length` Base62 characters derived from the
+/// leading bits of the UUID X, or `NULL` if X is not a valid UUID or `length`
+/// is not an INTEGER between 1 and 22. Each character carries about 5.95
+/// bits, so short slugs trade uniqueness for brevity: among `n` random UUIDs,
+/// a collision becomes likely once `n` approaches `62^(length / 2)`, e.g.
+/// around 240,000 UUIDs for 6 characters.
+unsafe extern "C" fn uuid_encode_url_path_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let length = unsafe { value_int64(*argv.add(1)) }
+        .filter(|length| (1..=MAX_SLUG_LENGTH).contains(length));
+    if let (Some(u), Some(length)) = (unsafe { parse_uuid_arg(argv, 0) }, length) {
+        unsafe {
+            result_text(ctx, base62_slug(&u, usize::try_from(length).unwrap()));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid_urn(X)` SQL function.
+///
+/// Returns the UUID X in its URN form, e.g.
+/// `urn:uuid:12345678-1234-1234-1234-123456789abc`, or `NULL` if X is not a
+/// valid UUID.
+unsafe extern "C" fn uuid_urn_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
+        unsafe {
+            result_text(ctx, u.urn().to_string());
+        }
+    } else {
+        unsafe {
+            result_invalid_uuid(ctx, *argv);
+        }
+    }
+}
+
+/// Number of Crockford Base32 characters encoding the 128 bits of a UUID, the
+/// first one carrying only 3 bits.
+const BASE32_LENGTH: usize = 26;
+
+/// Encodes a UUID in uppercase Crockford Base32, as in the text form of a
+/// ULID.
+#[allow(clippy::cast_possible_truncation)]
+fn crockford_base32(u: &Uuid) -> String {
+    let value = u.as_u128();
+    (0..BASE32_LENGTH)
+        .rev()
+        .map(|index| {
+            let digit = (value >> (5 * index)) as usize & 31;
+            char::from(CROCKFORD_BASE32[digit].to_ascii_uppercase())
+        })
+        .collect()
+}
+
+/// Decodes a UUID from its Crockford Base32 encoding.
+///
+/// Decoding is case-insensitive and, following Crockford, reads `I` and `L`
+/// as `1` and `O` as `0`.
+///
+/// # Returns
+/// * `Option<Uuid>` - The decoded UUID, or `None` if the text is not made of
+///   exactly 26 Base32 characters or its value does not fit in 128 bits.
+fn parse_crockford_base32(text: &str) -> Option<Uuid> {
+    if text.len() != BASE32_LENGTH {
+        return None;
+    }
+    let mut value: u128 = 0;
+    for (index, byte) in text.bytes().enumerate() {
+        let digit = match byte.to_ascii_lowercase() {
+            b'i' | b'l' => 1,
+            b'o' => 0,
+            lowercase => CROCKFORD_BASE32.iter().position(|&digit| digit == lowercase)?,
+        };
+        // The leading character only carries the 3 most significant bits.
+        if index == 0 && digit > 7 {
+            return None;
+        }
+        value = value << 5 | u128::try_from(digit).unwrap();
+    }
+    Some(Uuid::from_u128(value))
+}
+
+/// Implementation of the `uuid_base32(X)` SQL function.
+///
+/// Returns the UUID X as 26 uppercase Crockford Base32 characters, without
+/// padding, or `NULL` if X is not a valid UUID.
+unsafe extern "C" fn uuid_base32_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
+        unsafe {
+            result_text(ctx, crockford_base32(&u));
+        }
+    } else {
+        unsafe {
+            result_invalid_uuid(ctx, *argv);
+        }
+    }
+}
+
+/// Implementation of the `uuid_from_base32(X)` SQL function.
+///
+/// Decodes the Crockford Base32 text X produced by `uuid_base32` and returns
+/// the UUID as a canonical 36-character string, or `NULL` if X is malformed
+/// (see [`parse_crockford_base32`]).
+unsafe extern "C" fn uuid_from_base32_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { value_text(*argv) }.and_then(parse_crockford_base32) {
+        unsafe {
+            result_uuid_text(ctx, &u);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// URL-safe Base64 alphabet, as defined by RFC 4648.
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Number of unpadded Base64 characters encoding the 128 bits of a UUID, the
+/// last one carrying only 2 bits.
+const BASE64_LENGTH: usize = 22;
+
+/// Encodes a UUID in URL-safe Base64, without padding.
+#[allow(clippy::cast_possible_truncation)]
+fn base64_url(u: &Uuid) -> String {
+    let value = u.as_u128();
+    (0..BASE64_LENGTH)
+        .map(|index| {
+            let digit = if index + 1 < BASE64_LENGTH {
+                (value >> (122 - 6 * index)) as usize & 63
+            } else {
+                (value as usize & 3) << 4
+            };
+            char::from(BASE64_URL[digit])
+        })
+        .collect()
+}
+
+/// Decodes a UUID from its unpadded URL-safe Base64 encoding.
+///
+/// # Returns
+/// * `Option<Uuid>` - The decoded UUID, or `None` if the text is not made of
+///   exactly 22 URL-safe Base64 characters, or if the unused low 4 bits of
+///   its last character are not zero, so that each UUID has a single
+///   encoding.
+fn parse_base64_url(text: &str) -> Option<Uuid> {
+    if text.len() != BASE64_LENGTH {
+        return None;
+    }
+    let mut value: u128 = 0;
+    for (index, byte) in text.bytes().enumerate() {
+        let digit = u128::try_from(BASE64_URL.iter().position(|&digit| digit == byte)?).unwrap();
+        if index + 1 < BASE64_LENGTH {
+            value = value << 6 | digit;
+        } else if digit.trailing_zeros() >= 4 {
+            value = value << 2 | digit >> 4;
+        } else {
+            return None;
+        }
+    }
+    Some(Uuid::from_u128(value))
+}
+
+/// Implementation of the `uuid_base64(X)` SQL function.
+///
+/// Returns the UUID X as 22 URL-safe Base64 characters, without padding, or
+/// `NULL` if X is not a valid UUID.
+unsafe extern "C" fn uuid_base64_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
+        unsafe {
+            result_text(ctx, base64_url(&u));
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid_from_base64(X)` SQL function.
+///
+/// Decodes the URL-safe Base64 text X produced by `uuid_base64` and returns
+/// the UUID as a canonical 36-character string, or `NULL` if X is malformed
+/// (see [`parse_base64_url`]).
+unsafe extern "C" fn uuid_from_base64_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { value_text(*argv) }.and_then(parse_base64_url) {
+        unsafe {
+            result_uuid_text(ctx, &u);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+// --- SQL Functions (Checksums) ---
+
+/// Crockford's Base32 alphabet, in lowercase.
+const CROCKFORD_BASE32: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Computes the 2-character Base32 checksum of a UUID.
+///
+/// The checksum is the position-weighted sum of the 16 bytes modulo the prime
+/// 1021, encoded as two Crockford Base32 characters. Since both the weights
+/// and any difference between two bytes are smaller than 1021, altering a
+/// single byte of the UUID always changes its checksum.
+fn uuid_checksum(u: &Uuid) -> [u8; 2] {
+    let sum: u32 =
+        u.as_bytes().iter().zip(1..).map(|(&byte, weight)| u32::from(byte) * weight).sum();
+    let checksum = sum % 1021;
+    [CROCKFORD_BASE32[(checksum >> 5) as usize], CROCKFORD_BASE32[(checksum & 31) as usize]]
+}
+
+/// Implementation of the `uuid_checksum_text(X)` SQL function.
+///
+/// Returns the 32-character simple form of the UUID followed by a `-` and a
+/// 2-character Base32 checksum, e.g. `12345678123412341234123456789abc-3f`,
+/// or `NULL` if X is not a valid UUID.
+unsafe extern "C" fn uuid_checksum_text_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { parse_uuid_arg(argv, 0) } {
+        let [high, low] = uuid_checksum(&u);
+        let s = format!("{}-{}{}", u.simple(), char::from(high), char::from(low));
+        unsafe {
+            result_text(ctx, s);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid_verify_checksum_text(X)` SQL function.
+///
+/// Returns `1` if X is a UUID with a valid checksum as produced by
+/// `uuid_checksum_text`, and `0` otherwise. The comparison is
+/// case-insensitive.
+unsafe extern "C" fn uuid_verify_checksum_text_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let text = unsafe { value_text(*argv) };
+    let valid = text.and_then(|text| text.split_once('-')).is_some_and(|(id, checksum)| {
+        id.len() == 32
+            && Uuid::try_parse(id)
+                .is_ok_and(|u| checksum.as_bytes().eq_ignore_ascii_case(&uuid_checksum(&u)))
+    });
+    unsafe {
+        sqlite3_result_int(ctx, c_int::from(valid));
+    }
+}
+
+// --- SQL Functions (Introspection) ---
+
+/// SQL Function: `uuid_sql_manifest()`
+///
+/// Returns a text manifest with one line per SQL function registered by the
+/// extension on the connection, listing its signature as `name(n_arg)`
+/// followed by whether it is `deterministic` or `non-deterministic`, and by
+/// `aggregate` for the aggregate functions. The table-valued functions follow
+/// as `name table-valued`, and the collating sequence as `UUID collation`.
+/// Only the members of the [`FunctionSet`] registered on the connection are
+/// listed, as recorded in its [`Registration`].
+unsafe extern "C" fn uuid_sql_manifest_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    let set = unsafe { registration(ctx) }.set;
+    let mut manifest = String::new();
+    for function in FUNCTIONS.iter().filter(|function| set.contains(function.set)) {
+        let determinism =
+            if function.deterministic { "deterministic" } else { "non-deterministic" };
+        let _ = writeln!(
+            manifest,
+            "{}({}) {determinism}",
+            function.name.to_string_lossy(),
+            function.n_arg
+        );
+    }
+    for function in AGGREGATE_FUNCTIONS.iter().filter(|function| set.contains(function.set)) {
+        let _ = writeln!(
+            manifest,
+            "{}({}) deterministic aggregate",
+            function.name.to_string_lossy(),
+            function.n_arg
+        );
+    }
+    if set.contains(FunctionSet::TABLE_FUNCTIONS) {
+        for table_function in TABLE_FUNCTIONS {
+            let _ = writeln!(manifest, "{} table-valued", table_function.name.to_string_lossy());
+        }
+    }
+    if set.contains(FunctionSet::COLLATION) || set.contains(FunctionSet::TABLE_FUNCTIONS) {
+        let _ = writeln!(manifest, "{} collation", UUID_COLLATION.to_string_lossy());
+    }
+    unsafe {
+        result_text(ctx, manifest);
+    }
+}
+
+/// Source of randomness used by the `uuid` crate on this target.
+#[cfg(target_arch = "wasm32")]
+const RNG_BACKEND: &str = "crypto.getRandomValues";
+/// Source of randomness used by the `uuid` crate on this target.
+#[cfg(not(target_arch = "wasm32"))]
+const RNG_BACKEND: &str = "getrandom";
+
+/// Source of randomness of the UUIDv4 of connections initialised by
+/// [`register_with_seed`].
+const SEEDED_RNG_BACKEND: &str = "ChaCha8Rng";
+
+/// Source of time used by the `uuid` crate on this target.
+#[cfg(target_arch = "wasm32")]
+const CLOCK_BACKEND: &str = "Date.now";
+/// Source of time used by the `uuid` crate on this target.
+#[cfg(not(target_arch = "wasm32"))]
+const CLOCK_BACKEND: &str = "SystemTime::now";
+
+/// SQL Function: `uuid_probe()`
+///
+/// Returns a JSON object describing the runtime backends of the extension,
+/// useful to diagnose deployment issues such as a missing RNG or a coarse
+/// clock:
+/// - `version`: The version of this crate.
+/// - `rng`: The source of randomness of UUIDv4 and UUIDv7, or `ChaCha8Rng`
+///   on connections opened after [`register_with_seed`], whose UUIDv4 are
+///   seeded while their UUIDv7 still use the source of the target.
+/// - `clock`: The source of time of UUIDv7.
+/// - `monotonic_v7`: Whether UUIDv7 generated within the same millisecond
+///   are strictly increasing.
+unsafe extern "C" fn uuid_probe_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    let rng = if unsafe { registration(ctx) }.seeded { SEEDED_RNG_BACKEND } else { RNG_BACKEND };
+    let probe = format!(
+        r#"{{"version":"{}","rng":"{rng}","clock":"{CLOCK_BACKEND}","monotonic_v7":true}}"#,
+        env!("CARGO_PKG_VERSION"),
+    );
+    unsafe {
+        result_text(ctx, probe);
+    }
+}
+
+// --- Collations ---
+
+/// Name of the collating sequence comparing TEXT UUIDs by their value.
+const UUID_COLLATION: &CStr = c"UUID";
+
+/// Compares two TEXT values under the `UUID` collating sequence.
+///
+/// Values which parse as UUIDs, in any of the accepted text forms and in any
+/// case, are compared by their 16 bytes, which is the order of their BLOB
+/// form. They sort before values which are not UUIDs, which are compared
+/// bytewise among themselves.
+fn compare_uuid_text(a: &[u8], b: &[u8]) -> Ordering {
+    let parse = |bytes: &[u8]| core::str::from_utf8(bytes).ok().and_then(parse_uuid_str);
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.cmp(b),
     }
+}
 
-    let rc = unsafe {
-        sqlite3_create_function_v2(
-            db,
-            c"uuid7_blob".as_ptr(),
-            1,
-            deterministic,
-            ptr::null_mut(),
-            Some(uuid7_blob_func),
-            None,
-            None,
-            None,
-        )
+/// Implementation of the `UUID` collating sequence, see [`compare_uuid_text`].
+///
+/// SQLite only applies collating sequences to TEXT values: a BLOB never
+/// compares equal to a TEXT, whatever the collation, so columns mixing both
+/// forms should be normalized with `uuid_str` first.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw buffers provided
+/// by SQLite, which must hold `len_a` and `len_b` bytes respectively.
+unsafe extern "C" fn uuid_collation(
+    _p_arg: *mut c_void,
+    len_a: c_int,
+    a: *const c_void,
+    len_b: c_int,
+    b: *const c_void,
+) -> c_int {
+    let bytes = |len: c_int, ptr: *const c_void| -> &[u8] {
+        match usize::try_from(len) {
+            Ok(len) if len > 0 && !ptr.is_null() => unsafe {
+                slice::from_raw_parts(ptr.cast::<u8>(), len)
+            },
+            _ => &[],
+        }
     };
-    if rc != SQLITE_OK {
-        return rc;
+    c_int::from(compare_uuid_text(bytes(len_a, a), bytes(len_b, b)) as i8)
+}
+
+// --- Rust Helpers ---
+
+/// Generates `n` random UUIDv4s as 16-byte arrays.
+///
+/// The arrays use the same layout as `uuid_blob()`, so they can be bound
+/// directly as BLOB parameters, for instance through the `carray` or
+/// `rarray` table-valued functions to select many rows with a single
+/// `WHERE id IN carray(?1)` query instead of materializing the ids in a
+/// temporary table.
+///
+/// # Arguments
+/// * `n` - The number of UUIDs to generate.
+///
+/// # Returns
+/// * `Vec<[u8; 16]>` - The bytes of `n` random UUIDv4s.
+#[must_use]
+pub fn generate_uuid_blobs(n: usize) -> Vec<[u8; 16]> {
+    (0..n).map(|_| generate::new_v4_blob()).collect()
+}
+
+// --- Function Registry ---
+
+/// Signature shared by all the scalar SQL function implementations.
+type ScalarFunction = unsafe extern "C" fn(*mut sqlite3_context, c_int, *mut *mut sqlite3_value);
+
+/// Signature of the `xFinal` callback of the aggregate SQL functions.
+type FinalFunction = unsafe extern "C" fn(*mut sqlite3_context);
+
+/// A set of families of SQL functions, selecting which ones are registered.
+///
+/// Sets are combined with `|`, e.g. `FunctionSet::V7 | FunctionSet::CONVERSION`
+/// registers the UUIDv7 functions and the conversion helpers only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FunctionSet(u32);
+
+impl FunctionSet {
+    /// UUIDv7 generation and inspection: `uuid7`, `uuid7_blob`, `uuid7_at`,
+    /// `uuid7_timestamp`, the `uuid7_min` and `uuid7_max` aggregates, ...
+    pub const V7: Self = Self(1 << 0);
+    /// UUIDv4 generation: `uuid()` and `uuid_blob()`.
+    pub const V4: Self = Self(1 << 1);
+    /// UUIDv1 generation: `uuid_v1_with_clock_seq`, ...
+    pub const V1: Self = Self(1 << 2);
+    /// Name-based UUIDv3 and UUIDv5 generation, and the standard namespaces.
+    pub const NAME_BASED: Self = Self(1 << 3);
+    /// Conversions between representations: `uuid_str`, `uuid_blob(X)`,
+    /// integer blobs, GUID byte order, relaxed parsing, encodings and
+    /// checksums.
+    pub const CONVERSION: Self = Self(1 << 4);
+    /// Sentinels, inspection and comparison: `uuid_nil`, `uuid_version`,
+    /// `uuid_compare`, ...
+    pub const INSPECTION: Self = Self(1 << 5);
+    /// Pairing, anonymization, partitioning and introspection: `uuid_shard`,
+    /// `uuid7_strip`, `uuid_sql_manifest`, ...
+    pub const UTILITIES: Self = Self(1 << 6);
+    /// The table-valued functions: `uuid_test_vectors`, `uuid_parse_many` and
+    /// `uuid_series`. Also registers the `UUID` collating sequence, which the
+    /// `canonical` column of `uuid_test_vectors` is declared with.
+    pub const TABLE_FUNCTIONS: Self = Self(1 << 7);
+    /// The `UUID` collating sequence.
+    pub const COLLATION: Self = Self(1 << 8);
+    /// UUIDv8 generation: `uuid8` and `uuid8_blob`, with the `v8` feature.
+    #[cfg(feature = "v8")]
+    pub const V8: Self = Self(1 << 9);
+    /// All the functions and the collating sequence of the extension.
+    pub const ALL: Self = Self((1 << 10) - 1);
+
+    /// Returns whether all the functions of `other` belong to this set.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
     }
+}
 
-    // --- UUIDv4 Registration ---
+impl core::ops::BitOr for FunctionSet {
+    type Output = Self;
 
-    let rc = unsafe {
-        sqlite3_create_function_v2(
-            db,
-            c"uuid".as_ptr(),
-            0,
-            flags,
-            ptr::null_mut(),
-            Some(uuid_func),
-            None,
-            None,
-            None,
-        )
-    };
-    if rc != SQLITE_OK {
-        return rc;
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
     }
+}
 
-    let rc = unsafe {
-        sqlite3_create_function_v2(
-            db,
-            c"uuid_str".as_ptr(),
-            1,
-            deterministic,
-            ptr::null_mut(),
-            Some(uuid_str_func),
-            None,
-            None,
-            None,
-        )
-    };
+/// The [`FunctionSet`] registered by the entry points, as selected by the
+/// last call to [`register_function_set`] (all functions by default).
+static FUNCTION_SET: AtomicU32 = AtomicU32::new(FunctionSet::ALL.0);
+
+/// Description of a SQL function registered by the extension.
+struct SqlFunction {
+    /// Name of the function as seen from SQL.
+    name: &'static CStr,
+    /// Number of arguments accepted by this overload of the function.
+    n_arg: c_int,
+    /// Whether the function always returns the same output for the same
+    /// input, which allows SQLite to use it in indexes and constraints.
+    deterministic: bool,
+    /// The set of functions this one belongs to, see [`FunctionSet`].
+    set: FunctionSet,
+    /// Implementation of the function.
+    func: ScalarFunction,
+}
+
+/// Registry of all the SQL functions provided by the extension.
+const FUNCTIONS: &[SqlFunction] = &[
+    // UUIDv7
+    SqlFunction {
+        name: c"uuid7",
+        n_arg: 0,
+        deterministic: false,
+        set: FunctionSet::V7,
+        func: uuid7_func,
+    },
+    SqlFunction {
+        name: c"uuid7_blob",
+        n_arg: 0,
+        deterministic: false,
+        set: FunctionSet::V7,
+        func: uuid7_blob_func,
+    },
+    SqlFunction {
+        name: c"uuid7_blob",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::V7,
+        func: uuid7_blob_func,
+    },
+    SqlFunction {
+        name: c"uuid_generate_monotonic_text",
+        n_arg: 0,
+        deterministic: false,
+        set: FunctionSet::V7,
+        func: uuid_generate_monotonic_text_func,
+    },
+    SqlFunction {
+        name: c"uuid_now_v7_text_batch",
+        n_arg: 1,
+        deterministic: false,
+        set: FunctionSet::V7,
+        func: uuid_now_v7_text_batch_func,
+    },
+    SqlFunction {
+        name: c"uuid7_timestamp",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::V7,
+        func: uuid7_timestamp_func,
+    },
+    SqlFunction {
+        name: c"uuid7_datetime",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::V7,
+        func: uuid7_datetime_func,
+    },
+    SqlFunction {
+        name: c"uuid7_at",
+        n_arg: 1,
+        deterministic: false,
+        set: FunctionSet::V7,
+        func: uuid7_at_func,
+    },
+    SqlFunction {
+        name: c"uuid7_at_blob",
+        n_arg: 1,
+        deterministic: false,
+        set: FunctionSet::V7,
+        func: uuid7_at_blob_func,
+    },
+    // UUIDv4
+    SqlFunction {
+        name: c"uuid",
+        n_arg: 0,
+        deterministic: false,
+        set: FunctionSet::V4,
+        func: uuid_func,
+    },
+    SqlFunction {
+        name: c"uuid_str",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_str_func,
+    },
+    SqlFunction {
+        name: c"uuid_str",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_str_func,
+    },
+    SqlFunction {
+        name: c"uuid_blob",
+        n_arg: 0,
+        deterministic: false,
+        set: FunctionSet::V4,
+        func: uuid_blob_func,
+    },
+    SqlFunction {
+        name: c"uuid_blob",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_blob_func,
+    },
+    // UUIDv1
+    SqlFunction {
+        name: c"uuid_v1_with_clock_seq",
+        n_arg: 2,
+        deterministic: false,
+        set: FunctionSet::V1,
+        func: uuid_v1_with_clock_seq_func,
+    },
+    SqlFunction {
+        name: c"uuid_v1_with_clock_seq_blob",
+        n_arg: 2,
+        deterministic: false,
+        set: FunctionSet::V1,
+        func: uuid_v1_with_clock_seq_blob_func,
+    },
+    SqlFunction {
+        name: c"uuid_mask_node",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::V1,
+        func: uuid_mask_node_func,
+    },
+    // UUIDv8
+    #[cfg(feature = "v8")]
+    SqlFunction {
+        name: c"uuid8",
+        n_arg: 1,
+        deterministic: false,
+        set: FunctionSet::V8,
+        func: v8::uuid8_func,
+    },
+    #[cfg(feature = "v8")]
+    SqlFunction {
+        name: c"uuid8_blob",
+        n_arg: 1,
+        deterministic: false,
+        set: FunctionSet::V8,
+        func: v8::uuid8_blob_func,
+    },
+    // UUIDv3
+    SqlFunction {
+        name: c"uuid3",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::NAME_BASED,
+        func: uuid3_func,
+    },
+    SqlFunction {
+        name: c"uuid3_blob",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::NAME_BASED,
+        func: uuid3_blob_func,
+    },
+    // UUIDv5
+    SqlFunction {
+        name: c"uuid5",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::NAME_BASED,
+        func: uuid5_func,
+    },
+    SqlFunction {
+        name: c"uuid5_blob",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::NAME_BASED,
+        func: uuid5_blob_func,
+    },
+    // Namespaces
+    SqlFunction {
+        name: c"uuid_ns_dns",
+        n_arg: 0,
+        deterministic: true,
+        set: FunctionSet::NAME_BASED,
+        func: uuid_ns_dns_func,
+    },
+    SqlFunction {
+        name: c"uuid_ns_url",
+        n_arg: 0,
+        deterministic: true,
+        set: FunctionSet::NAME_BASED,
+        func: uuid_ns_url_func,
+    },
+    SqlFunction {
+        name: c"uuid_ns_oid",
+        n_arg: 0,
+        deterministic: true,
+        set: FunctionSet::NAME_BASED,
+        func: uuid_ns_oid_func,
+    },
+    SqlFunction {
+        name: c"uuid_ns_x500",
+        n_arg: 0,
+        deterministic: true,
+        set: FunctionSet::NAME_BASED,
+        func: uuid_ns_x500_func,
+    },
+    // Sentinels
+    SqlFunction {
+        name: c"uuid_nil",
+        n_arg: 0,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_nil_func,
+    },
+    SqlFunction {
+        name: c"uuid_nil_blob",
+        n_arg: 0,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_nil_blob_func,
+    },
+    SqlFunction {
+        name: c"uuid_max",
+        n_arg: 0,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_max_func,
+    },
+    SqlFunction {
+        name: c"uuid_max_blob",
+        n_arg: 0,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_max_blob_func,
+    },
+    // Inspection
+    SqlFunction {
+        name: c"uuid_version",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_version_func,
+    },
+    SqlFunction {
+        name: c"uuid_version_of_blob",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_version_of_blob_func,
+    },
+    SqlFunction {
+        name: c"uuid_variant",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_variant_func,
+    },
+    SqlFunction {
+        name: c"uuid_is_valid",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_is_valid_func,
+    },
+    SqlFunction {
+        name: c"uuid_timestamp_resolution",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_timestamp_resolution_func,
+    },
+    SqlFunction {
+        name: c"uuid_extract_timestamp",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_extract_timestamp_func,
+    },
+    SqlFunction {
+        name: c"uuid_clock_seq",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_clock_seq_func,
+    },
+    SqlFunction {
+        name: c"uuid_node",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_node_func,
+    },
+    // Comparison
+    SqlFunction {
+        name: c"uuid_compare",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_compare_func,
+    },
+    SqlFunction {
+        name: c"uuid_compare_version",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_compare_version_func,
+    },
+    SqlFunction {
+        name: c"uuid_eq",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::INSPECTION,
+        func: uuid_eq_func,
+    },
+    // Integer blobs
+    SqlFunction {
+        name: c"uuid_as_integer_blob",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_as_integer_blob_func,
+    },
+    SqlFunction {
+        name: c"uuid_from_integer_blob",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_from_integer_blob_func,
+    },
+    // GUID byte order
+    SqlFunction {
+        name: c"uuid_to_guid",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_to_guid_func,
+    },
+    SqlFunction {
+        name: c"uuid_guid_to_uuid",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_guid_to_uuid_func,
+    },
+    // Relaxed parsing
+    SqlFunction {
+        name: c"uuid_parse_relaxed_blob",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_parse_relaxed_blob_func,
+    },
+    // Pairs
+    SqlFunction {
+        name: c"uuid_dedup_pair_key",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::UTILITIES,
+        func: uuid_dedup_pair_key_func,
+    },
+    // Anonymization
+    SqlFunction {
+        name: c"uuid7_strip",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::UTILITIES,
+        func: uuid7_strip_func,
+    },
+    // Partitioning
+    SqlFunction {
+        name: c"uuid_hamming_bucket",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::UTILITIES,
+        func: uuid_hamming_bucket_func,
+    },
+    SqlFunction {
+        name: c"uuid_shard",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::UTILITIES,
+        func: uuid_shard_func,
+    },
+    SqlFunction {
+        name: c"uuid_generate_for_shard",
+        n_arg: 2,
+        deterministic: false,
+        set: FunctionSet::UTILITIES,
+        func: uuid_generate_for_shard_func,
+    },
+    // Encodings
+    SqlFunction {
+        name: c"uuid_encode_url_path",
+        n_arg: 2,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_encode_url_path_func,
+    },
+    SqlFunction {
+        name: c"uuid_urn",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_urn_func,
+    },
+    SqlFunction {
+        name: c"uuid_base32",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_base32_func,
+    },
+    SqlFunction {
+        name: c"uuid_from_base32",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_from_base32_func,
+    },
+    SqlFunction {
+        name: c"uuid_base64",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_base64_func,
+    },
+    SqlFunction {
+        name: c"uuid_from_base64",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_from_base64_func,
+    },
+    // Checksums
+    SqlFunction {
+        name: c"uuid_checksum_text",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_checksum_text_func,
+    },
+    SqlFunction {
+        name: c"uuid_verify_checksum_text",
+        n_arg: 1,
+        deterministic: true,
+        set: FunctionSet::CONVERSION,
+        func: uuid_verify_checksum_text_func,
+    },
+    // Introspection
+    SqlFunction {
+        name: c"uuid_sql_manifest",
+        n_arg: 0,
+        deterministic: true,
+        set: FunctionSet::UTILITIES,
+        func: uuid_sql_manifest_func,
+    },
+    SqlFunction {
+        name: c"uuid_probe",
+        n_arg: 0,
+        deterministic: false,
+        set: FunctionSet::UTILITIES,
+        func: uuid_probe_func,
+    },
+];
+
+/// Description of an aggregate SQL function registered by the extension.
+struct AggregateFunction {
+    /// Name of the function as seen from SQL.
+    name: &'static CStr,
+    /// Number of arguments accepted by this overload of the function.
+    n_arg: c_int,
+    /// The set of functions this one belongs to, see [`FunctionSet`].
+    set: FunctionSet,
+    /// Callback folding a row into the state of its group.
+    step: ScalarFunction,
+    /// Callback returning the result of a group from its state.
+    finalize: FinalFunction,
+}
+
+/// Registry of all the aggregate SQL functions provided by the extension,
+/// all of which are deterministic.
+const AGGREGATE_FUNCTIONS: &[AggregateFunction] = &[
+    AggregateFunction {
+        name: c"uuid7_min",
+        n_arg: 1,
+        set: FunctionSet::V7,
+        step: uuid7_min_step,
+        finalize: extremum_final,
+    },
+    AggregateFunction {
+        name: c"uuid7_max",
+        n_arg: 1,
+        set: FunctionSet::V7,
+        step: uuid7_max_step,
+        finalize: extremum_final,
+    },
+];
+
+/// Registry of all the table-valued functions provided by the extension.
+static TABLE_FUNCTIONS: &[TableFunction] = &[
+    TableFunction { name: c"uuid_test_vectors", module: &test_vectors::MODULE },
+    TableFunction { name: c"uuid_parse_many", module: &parse_many::MODULE },
+    TableFunction { name: c"uuid_series", module: &series::MODULE },
+];
+
+// --- Extension Entry Point ---
+
+/// SQLite Extension Entry Point: `sqlite3_uuid_init`
+///
+/// Registers all the SQL functions listed in the extension registry with the
+/// SQLite database connection, among which:
+/// - `uuid`
+/// - `uuid_str`
+/// - `uuid_blob`
+/// - `uuid7`
+/// - `uuid7_blob`
+/// - `uuid_sql_manifest`
+///
+/// It also registers the table-valued functions listed in the extension
+/// registry, such as `uuid_test_vectors` and `uuid_parse_many`, and the `UUID`
+/// collating sequence. After [`register_function_set`], only the selected
+/// [`FunctionSet`] is registered.
+///
+/// # Arguments
+/// * `db` - The SQLite database connection.
+/// * `pz_err_msg` - Pointer to error message pointer. On failure, it receives
+///   a message allocated with `sqlite3_malloc` naming the function or module
+///   which could not be registered, such as `failed to register function
+///   uuid7_blob/1`, to be freed by the caller with `sqlite3_free`. On success,
+///   it is left untouched.
+/// * `p_api` - Pointer to the SQLite API routines, through which all calls
+///   are dispatched when built as a native loadable extension with the
+///   `loadable-ext` feature. Unused in WASM, where it may be null.
+///
+/// # Returns
+/// * `SQLITE_OK` on success, or an error code.
+///
+/// # Safety
+/// This function is unsafe because it interacts with raw SQLite pointers.
+/// It assumes `db` is a valid SQLite database connection.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqlite3_uuid_init(
+    db: *mut sqlite3,
+    pz_err_msg: *mut *mut c_char,
+    p_api: *const sqlite3_api_routines,
+) -> c_int {
+    let set = FunctionSet(FUNCTION_SET.load(AtomicOrdering::Relaxed));
+    let registration = Registration { set, strict: false, seeded: false };
+    unsafe { init_connection(db, registration, pz_err_msg, p_api) }
+}
+
+/// SQLite Extension Entry Point: `sqlite3_uuid_init_strict`
+///
+/// Same as [`sqlite3_uuid_init`], but the functions converting a UUID
+/// argument (`uuid_str`, `uuid_blob`, `uuid7_blob`, `uuid_as_integer_blob`,
+/// `uuid_version`, `uuid_variant`, `uuid_compare`, `uuid_to_guid`,
+/// `uuid_urn` and `uuid_base32`) and the `uuid7_min` and `uuid7_max`
+/// aggregates raise an `invalid UUID argument` error instead of returning
+/// `NULL` when it cannot be parsed, so that malformed data does not go
+/// unnoticed. `NULL` arguments still yield `NULL`.
+///
+/// # Arguments
+/// * `db` - The SQLite database connection.
+/// * `pz_err_msg` - Pointer to error message pointer. On failure, it receives
+///   a message allocated with `sqlite3_malloc` naming the function or module
+///   which could not be registered, such as `failed to register function
+///   uuid7_blob/1`, to be freed by the caller with `sqlite3_free`. On success,
+///   it is left untouched.
+/// * `p_api` - Pointer to the SQLite API routines, through which all calls
+///   are dispatched when built as a native loadable extension with the
+///   `loadable-ext` feature. Unused in WASM, where it may be null.
+///
+/// # Returns
+/// * `SQLITE_OK` on success, or an error code.
+///
+/// # Safety
+/// This function is unsafe because it interacts with raw SQLite pointers.
+/// It assumes `db` is a valid SQLite database connection.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqlite3_uuid_init_strict(
+    db: *mut sqlite3,
+    pz_err_msg: *mut *mut c_char,
+    p_api: *const sqlite3_api_routines,
+) -> c_int {
+    let set = FunctionSet(FUNCTION_SET.load(AtomicOrdering::Relaxed));
+    let registration = Registration { set, strict: true, seeded: false };
+    unsafe { init_connection(db, registration, pz_err_msg, p_api) }
+}
+
+/// Initialises the SQLite API routines, then registers the functions with a
+/// database connection, as the entry points of the extension do.
+///
+/// # Arguments
+/// * `db` - The SQLite database connection.
+/// * `registration` - The options of the connection (see
+///   [`register_functions`]).
+/// * `pz_err_msg` - Pointer to error message pointer, receiving a description
+///   of the first failure, if any.
+/// * `p_api` - Pointer to the SQLite API routines (see [`ffi::init`]).
+///
+/// # Returns
+/// * `SQLITE_OK` on success, or the first error code encountered.
+///
+/// # Safety
+/// This function is unsafe because it interacts with raw SQLite pointers.
+/// It assumes `db` is a valid SQLite database connection.
+unsafe fn init_connection(
+    db: *mut sqlite3,
+    registration: Registration,
+    pz_err_msg: *mut *mut c_char,
+    p_api: *const sqlite3_api_routines,
+) -> c_int {
+    let rc = unsafe { ffi::init(p_api, pz_err_msg) };
     if rc != SQLITE_OK {
         return rc;
     }
+    unsafe { register_functions(db, registration, pz_err_msg) }
+}
+
+/// Registers the scalar and table-valued functions of the extension registry,
+/// as well as the `UUID` collating sequence, with a database connection.
+///
+/// Only the members of the [`FunctionSet`] of `registration` are registered.
+///
+/// # Arguments
+/// * `db` - The SQLite database connection.
+/// * `registration` - The options of the connection, of which each scalar and
+///   aggregate function gets a copy as its user data (see [`registration`]).
+/// * `pz_err_msg` - Pointer to error message pointer, receiving a description
+///   of the first registration which failed, if any (see [`set_error_message`]).
+///
+/// # Returns
+/// * `SQLITE_OK` on success, or the first error code encountered.
+///
+/// # Safety
+/// This function is unsafe because it interacts with raw SQLite pointers.
+/// It assumes `db` is a valid SQLite database connection.
+unsafe fn register_functions(
+    db: *mut sqlite3,
+    registration: Registration,
+    pz_err_msg: *mut *mut c_char,
+) -> c_int {
+    let set = registration.set;
+    // On failure, SQLite releases the copy through `destroy_registration`.
+    let p_app = || Box::into_raw(Box::new(registration)).cast::<c_void>();
+    let flags = SQLITE_UTF8 | SQLITE_INNOCUOUS;
+
+    for function in FUNCTIONS.iter().filter(|function| set.contains(function.set)) {
+        let flags = if function.deterministic { flags | SQLITE_DETERMINISTIC } else { flags };
+        let rc = unsafe {
+            sqlite3_create_function_v2(
+                db,
+                function.name.as_ptr(),
+                function.n_arg,
+                flags,
+                p_app(),
+                Some(function.func),
+                None,
+                None,
+                Some(destroy_registration),
+            )
+        };
+        if rc != SQLITE_OK {
+            let name = function.name.to_string_lossy();
+            let message = format!("failed to register function {name}/{}", function.n_arg);
+            unsafe { set_error_message(pz_err_msg, &message) };
+            return rc;
+        }
+    }
+
+    for function in AGGREGATE_FUNCTIONS.iter().filter(|function| set.contains(function.set)) {
+        let rc = unsafe {
+            sqlite3_create_function_v2(
+                db,
+                function.name.as_ptr(),
+                function.n_arg,
+                flags | SQLITE_DETERMINISTIC,
+                p_app(),
+                None,
+                Some(function.step),
+                Some(function.finalize),
+                Some(destroy_registration),
+            )
+        };
+        if rc != SQLITE_OK {
+            let name = function.name.to_string_lossy();
+            let message = format!("failed to register aggregate {name}/{}", function.n_arg);
+            unsafe { set_error_message(pz_err_msg, &message) };
+            return rc;
+        }
+    }
+
+    let table_functions =
+        if set.contains(FunctionSet::TABLE_FUNCTIONS) { TABLE_FUNCTIONS } else { &[] };
+    for table_function in table_functions {
+        let rc = unsafe {
+            sqlite3_create_module_v2(
+                db,
+                table_function.name.as_ptr(),
+                table_function.module,
+                ptr::null_mut(),
+                None,
+            )
+        };
+        if rc != SQLITE_OK {
+            let name = table_function.name.to_string_lossy();
+            unsafe { set_error_message(pz_err_msg, &format!("failed to register module {name}")) };
+            return rc;
+        }
+    }
 
+    if !set.contains(FunctionSet::COLLATION) && !set.contains(FunctionSet::TABLE_FUNCTIONS) {
+        return SQLITE_OK;
+    }
     let rc = unsafe {
-        sqlite3_create_function_v2(
+        sqlite3_create_collation_v2(
             db,
-            c"uuid_blob".as_ptr(),
-            0,
-            flags,
+            UUID_COLLATION.as_ptr(),
+            SQLITE_UTF8,
             ptr::null_mut(),
-            Some(uuid_blob_func),
-            None,
-            None,
+            Some(uuid_collation),
             None,
         )
     };
     if rc != SQLITE_OK {
+        let name = UUID_COLLATION.to_string_lossy();
+        unsafe { set_error_message(pz_err_msg, &format!("failed to register collation {name}")) };
         return rc;
     }
 
+    SQLITE_OK
+}
+
+/// Stores an error message in the `pz_err_msg` out-parameter of an entry point.
+///
+/// Following the SQLite convention, the message is a NUL-terminated string
+/// allocated with `sqlite3_malloc`, which the caller frees with
+/// `sqlite3_free`. Nothing is stored if `pz_err_msg` is null or the
+/// allocation fails.
+///
+/// # Safety
+/// This function is unsafe because it writes through the raw `pz_err_msg`
+/// pointer, which must be null or valid for writes.
+unsafe fn set_error_message(pz_err_msg: *mut *mut c_char, message: &str) {
+    if pz_err_msg.is_null() {
+        return;
+    }
+    let Ok(size) = c_int::try_from(message.len() + 1) else {
+        return;
+    };
+    let buffer = unsafe { sqlite3_malloc(size) }.cast::<u8>();
+    if buffer.is_null() {
+        return;
+    }
     unsafe {
-        sqlite3_create_function_v2(
-            db,
-            c"uuid_blob".as_ptr(),
-            1,
-            deterministic,
-            ptr::null_mut(),
-            Some(uuid_blob_func),
-            None,
-            None,
-            None,
-        )
+        ptr::copy_nonoverlapping(message.as_ptr(), buffer, message.len());
+        *buffer.add(message.len()) = 0;
+        *pz_err_msg = buffer.cast::<c_char>();
+    }
+}
+
+/// Error returned by the `register*` helpers when SQLite rejects the
+/// registration of the extension.
+///
+/// Wraps the SQLite result code, which remains available through
+/// [`RegisterError::code`], while its [`Display`](core::fmt::Display)
+/// implementation describes the common codes in plain words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RegisterError(c_int);
+
+impl RegisterError {
+    /// Wraps a SQLite result code.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The SQLite result code, possibly an extended one.
+    #[must_use]
+    pub const fn new(code: c_int) -> Self {
+        Self(code)
+    }
+
+    /// Returns the SQLite result code. Learn more about SQLite result codes
+    /// [here](https://www.sqlite.org/rescode.html).
+    #[must_use]
+    pub const fn code(self) -> c_int {
+        self.0
+    }
+}
+
+impl From<RegisterError> for c_int {
+    fn from(error: RegisterError) -> Self {
+        error.0
+    }
+}
+
+impl core::fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Extended result codes carry their primary code in the low byte.
+        let description = match self.0 & 0xFF {
+            SQLITE_ERROR => "generic error",
+            SQLITE_NOMEM => "out of memory",
+            SQLITE_BUSY => "database is busy",
+            SQLITE_LOCKED => "database table is locked",
+            SQLITE_MISUSE => "bad parameter or other API misuse",
+            _ => "unknown error",
+        };
+        write!(f, "failed to register the extension: {description} (SQLite result code {})", self.0)
     }
 }
 
+impl core::error::Error for RegisterError {}
+
+/// Whether [`sqlite3_uuid_init`] is installed as an auto-extension, by
+/// [`register`] or [`register_function_set`].
+#[cfg(target_arch = "wasm32")]
+static REGISTERED: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
 /// Rust-friendly helper to register the extension.
 ///
-/// # Returns
+/// Registering is idempotent: once the auto-extension is installed, further
+/// calls return `Ok(())` without installing it again, until [`unregister`]
+/// or one of the other `register*` helpers replaces it.
+///
+/// # Safety
+///
+/// This function is unsafe because it calls the unsafe `sqlite3_uuid_init`
+/// function.
+///
+/// # Errors
+///
+/// * Returns `Ok(())` if the extension was registered successfully.
+/// * Returns `Err(RegisterError)` wrapping the SQLite result code if registration failed.
+#[cfg(target_arch = "wasm32")]
+pub unsafe fn register() -> Result<(), RegisterError> {
+    unsafe { register_function_set(FunctionSet::ALL) }
+}
+
+/// Rust-friendly helper to register the extension in strict mode.
+///
+/// Same as [`register`], but new connections get the functions registered by
+/// [`sqlite3_uuid_init_strict`], which raise an error on malformed UUID
+/// arguments instead of returning `NULL`. Calling [`register`] afterwards
+/// switches new connections back to the default mode.
+///
+/// # Safety
+///
+/// This function is unsafe because it calls the unsafe
+/// `sqlite3_uuid_init_strict` function.
+///
+/// # Errors
+///
+/// * Returns `Ok(())` if the extension was registered successfully.
+/// * Returns `Err(RegisterError)` wrapping the SQLite result code if registration failed.
+#[cfg(target_arch = "wasm32")]
+pub unsafe fn register_strict() -> Result<(), RegisterError> {
+    FUNCTION_SET.store(FunctionSet::ALL.0, AtomicOrdering::Relaxed);
+    unsafe {
+        ffi::sqlite3_cancel_auto_extension(Some(sqlite3_uuid_init));
+        ffi::sqlite3_cancel_auto_extension(Some(seeded::sqlite3_uuid_init_seeded));
+    }
+    REGISTERED.store(false, AtomicOrdering::Release);
+    let status = unsafe { ffi::sqlite3_auto_extension(Some(sqlite3_uuid_init_strict)) };
+    if status == SQLITE_OK { Ok(()) } else { Err(RegisterError(status)) }
+}
+
+/// Rust-friendly helper to register the extension with reproducible UUIDv4.
+///
+/// Same as [`register`], but on every connection opened afterwards `uuid()`,
+/// `uuid_blob()` and the UUIDv4 of `uuid_series` draw their random bits from
+/// a ChaCha8 generator seeded with `seed`, instead of the OS random number
+/// generator. Each connection
+/// has its own generator, so connections opened with the same seed produce
+/// the same sequence of UUIDs. This is meant for tests asserting on generated
+/// identifiers: the values are predictable, and must never be used where
+/// UUIDs need to be unguessable. Calling [`register`] afterwards switches new
+/// connections back to the OS random number generator.
+///
+/// # Arguments
+///
+/// * `seed` - The seed of the generator of each new connection.
+///
+/// # Safety
+///
+/// This function is unsafe because it calls the unsafe `sqlite3_uuid_init`
+/// function.
+///
+/// # Errors
+///
+/// * Returns `Ok(())` if the extension was registered successfully.
+/// * Returns `Err(RegisterError)` wrapping the SQLite result code if registration failed.
+#[cfg(target_arch = "wasm32")]
+pub unsafe fn register_with_seed(seed: u64) -> Result<(), RegisterError> {
+    FUNCTION_SET.store(FunctionSet::ALL.0, AtomicOrdering::Relaxed);
+    seeded::SEED.store(seed, AtomicOrdering::Relaxed);
+    unsafe {
+        ffi::sqlite3_cancel_auto_extension(Some(sqlite3_uuid_init));
+        ffi::sqlite3_cancel_auto_extension(Some(sqlite3_uuid_init_strict));
+    }
+    REGISTERED.store(false, AtomicOrdering::Release);
+    let status = unsafe { ffi::sqlite3_auto_extension(Some(seeded::sqlite3_uuid_init_seeded)) };
+    if status == SQLITE_OK { Ok(()) } else { Err(RegisterError(status)) }
+}
+
+/// Rust-friendly helper to register a subset of the extension.
 ///
-/// * `c_int` - Result code from registering the extension.
+/// Same as [`register`], but new connections only get the functions of
+/// `set`, e.g. `FunctionSet::V7` for just the UUIDv7 functions, leaving the
+/// others undefined. Calling [`register`] afterwards switches new connections
+/// back to the full set of functions. The selection does not apply to
+/// [`register_on`], see [`register_on_with_set`] instead. Like [`register`],
+/// this is idempotent: a new selection applies to the connections opened
+/// afterwards without installing the auto-extension again.
+///
+/// # Arguments
+///
+/// * `set` - The families of functions to register.
 ///
 /// # Safety
 ///
@@ -340,8 +3087,106 @@ pub unsafe extern "C" fn sqlite3_uuid_init(
 /// # Errors
 ///
 /// * Returns `Ok(())` if the extension was registered successfully.
-/// * Returns `Err(c_int)` with the SQLite error code if registration failed. Learn more about SQLite error codes [here](https://www.sqlite.org/rescode.html).
-pub unsafe fn register() -> Result<(), c_int> {
-    let status = unsafe { sqlite_wasm_rs::sqlite3_auto_extension(Some(sqlite3_uuid_init)) };
-    if status == SQLITE_OK { Ok(()) } else { Err(status) }
+/// * Returns `Err(RegisterError)` wrapping the SQLite result code if registration failed.
+#[cfg(target_arch = "wasm32")]
+pub unsafe fn register_function_set(set: FunctionSet) -> Result<(), RegisterError> {
+    FUNCTION_SET.store(set.0, AtomicOrdering::Relaxed);
+    if REGISTERED.swap(true, AtomicOrdering::AcqRel) {
+        return Ok(());
+    }
+    unsafe {
+        ffi::sqlite3_cancel_auto_extension(Some(sqlite3_uuid_init_strict));
+        ffi::sqlite3_cancel_auto_extension(Some(seeded::sqlite3_uuid_init_seeded));
+    }
+    let status = unsafe { ffi::sqlite3_auto_extension(Some(sqlite3_uuid_init)) };
+    if status == SQLITE_OK {
+        Ok(())
+    } else {
+        REGISTERED.store(false, AtomicOrdering::Release);
+        Err(RegisterError(status))
+    }
+}
+
+/// Rust-friendly helper to unregister the extension.
+///
+/// Removes the auto-extension installed by any of the `register*` helpers,
+/// so that connections opened afterwards no longer get the functions, while
+/// those already open keep them. Calling it when the extension is not
+/// registered does nothing.
+///
+/// Prefer this to `sqlite3_reset_auto_extension`, which [`register`] cannot
+/// detect: after a reset, [`register`] would consider the extension still
+/// installed.
+///
+/// # Safety
+///
+/// This function is unsafe because it calls the unsafe
+/// `sqlite3_cancel_auto_extension` function.
+#[cfg(target_arch = "wasm32")]
+pub unsafe fn unregister() {
+    unsafe {
+        ffi::sqlite3_cancel_auto_extension(Some(sqlite3_uuid_init));
+        ffi::sqlite3_cancel_auto_extension(Some(sqlite3_uuid_init_strict));
+        ffi::sqlite3_cancel_auto_extension(Some(seeded::sqlite3_uuid_init_seeded));
+    }
+    REGISTERED.store(false, AtomicOrdering::Release);
+}
+
+/// Rust-friendly helper to register the extension on a single connection.
+///
+/// Unlike [`register`], which installs an auto-extension affecting every
+/// connection opened afterwards in the process, this registers the functions
+/// on `db` only. All of them are registered, regardless of any selection made
+/// with [`register_function_set`].
+///
+/// # Arguments
+///
+/// * `db` - The SQLite database connection, e.g. obtained from rusqlite's
+///   `Connection::handle`.
+///
+/// # Safety
+///
+/// `db` must be a valid, open SQLite database connection, and must not be
+/// used concurrently from another thread while the functions are registered.
+/// The registered functions are bound to the connection and are dropped when
+/// it is closed.
+///
+/// # Errors
+///
+/// * Returns `Ok(())` if the extension was registered successfully.
+/// * Returns `Err(RegisterError)` wrapping the SQLite result code if registration failed.
+#[cfg(target_arch = "wasm32")]
+pub unsafe fn register_on(db: *mut sqlite3) -> Result<(), RegisterError> {
+    unsafe { register_on_with_set(db, FunctionSet::ALL) }
+}
+
+/// Rust-friendly helper to register a subset of the extension on a single
+/// connection.
+///
+/// Same as [`register_on`], but only the functions of `set` are registered on
+/// `db`, independently of the selection made with [`register_function_set`]
+/// for the connections opened through the auto-extension.
+///
+/// # Arguments
+///
+/// * `db` - The SQLite database connection, e.g. obtained from rusqlite's
+///   `Connection::handle`.
+/// * `set` - The families of functions to register.
+///
+/// # Safety
+///
+/// Same requirements as [`register_on`].
+///
+/// # Errors
+///
+/// * Returns `Ok(())` if the extension was registered successfully.
+/// * Returns `Err(RegisterError)` wrapping the SQLite result code if registration failed.
+#[cfg(target_arch = "wasm32")]
+pub unsafe fn register_on_with_set(
+    db: *mut sqlite3,
+    set: FunctionSet,
+) -> Result<(), RegisterError> {
+    let registration = Registration { set, strict: false, seeded: false };
+    let status = unsafe { init_connection(db, registration, ptr::null_mut(), ptr::null()) };
+    if status == SQLITE_OK { Ok(()) } else { Err(RegisterError(status)) }
 }
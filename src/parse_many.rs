@@ -0,0 +1,190 @@
+//! Table-valued function `uuid_parse_many(text, separator)`, splitting a
+//! delimited list of UUIDs (such as a CSV cell) into one row per token.
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    ffi::{CStr, c_char, c_int, c_void},
+    ptr,
+};
+
+use crate::ffi::{
+    SQLITE_OK, sqlite3, sqlite3_context, sqlite3_index_info, sqlite3_int64, sqlite3_module,
+    sqlite3_result_int, sqlite3_result_null, sqlite3_value, sqlite3_vtab, sqlite3_vtab_cursor,
+};
+use uuid::Uuid;
+
+use crate::vtab;
+
+/// Schema of the `uuid_parse_many` virtual table.
+const SCHEMA: &CStr =
+    c"CREATE TABLE x(value TEXT, ok INTEGER, text TEXT HIDDEN, separator TEXT HIDDEN)";
+
+/// Index of the `value` column.
+const COLUMN_VALUE: c_int = 0;
+/// Index of the `ok` column.
+const COLUMN_OK: c_int = 1;
+/// Index of the hidden `text` column, the first argument of the function.
+const COLUMN_TEXT: c_int = 2;
+/// Index of the hidden `separator` column, the second argument of the
+/// function.
+const COLUMN_SEPARATOR: c_int = 3;
+
+/// Separator used when the function is called without one.
+const DEFAULT_SEPARATOR: &str = ",";
+
+/// Cursor iterating over the tokens of the split text.
+#[repr(C)]
+struct Cursor {
+    /// Base class, must come first.
+    base: sqlite3_vtab_cursor,
+    /// The text argument, returned by the hidden `text` column.
+    text: String,
+    /// The separator argument, returned by the hidden `separator` column.
+    separator: String,
+    /// The parsed tokens, `None` for those which are not valid UUIDs.
+    tokens: Vec<Option<Uuid>>,
+    /// Index of the current row in `tokens`.
+    row: usize,
+}
+
+/// The `sqlite3_module` implementing `uuid_parse_many`.
+pub(crate) static MODULE: sqlite3_module = sqlite3_module {
+    xConnect: Some(connect),
+    xBestIndex: Some(best_index),
+    xOpen: Some(open),
+    xClose: Some(close),
+    xFilter: Some(filter),
+    xNext: Some(next),
+    xEof: Some(eof),
+    xColumn: Some(column),
+    xRowid: Some(rowid),
+    ..vtab::READ_ONLY_MODULE
+};
+
+/// `xConnect` callback declaring the schema of the virtual table.
+unsafe extern "C" fn connect(
+    db: *mut sqlite3,
+    _p_aux: *mut c_void,
+    _argc: c_int,
+    _argv: *const *const c_char,
+    pp_vtab: *mut *mut sqlite3_vtab,
+    _pz_err: *mut *mut c_char,
+) -> c_int {
+    unsafe { vtab::connect(db, SCHEMA, pp_vtab) }
+}
+
+/// `xBestIndex` callback binding the `text` and `separator` arguments.
+unsafe extern "C" fn best_index(
+    _p_vtab: *mut sqlite3_vtab,
+    p_info: *mut sqlite3_index_info,
+) -> c_int {
+    let info = unsafe { &mut *p_info };
+    match unsafe { vtab::bind_arguments(info, COLUMN_TEXT, 2) } {
+        Ok(mask) => {
+            info.idxNum = mask;
+            SQLITE_OK
+        }
+        Err(rc) => rc,
+    }
+}
+
+/// `xOpen` callback allocating a new [`Cursor`].
+unsafe extern "C" fn open(
+    _p_vtab: *mut sqlite3_vtab,
+    pp_cursor: *mut *mut sqlite3_vtab_cursor,
+) -> c_int {
+    let cursor = Cursor {
+        base: sqlite3_vtab_cursor { pVtab: ptr::null_mut() },
+        text: String::new(),
+        separator: String::new(),
+        tokens: Vec::new(),
+        row: 0,
+    };
+    unsafe { vtab::open(cursor, pp_cursor) }
+}
+
+/// `xClose` callback releasing a [`Cursor`].
+unsafe extern "C" fn close(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    unsafe { vtab::close::<Cursor>(p_cursor) }
+}
+
+/// `xFilter` callback splitting the text and parsing each of its tokens.
+///
+/// Tokens are trimmed of surrounding whitespace before being parsed. A `NULL`
+/// or missing text yields no rows, while a missing separator defaults to a
+/// comma and an empty one leaves the text unsplit.
+unsafe extern "C" fn filter(
+    p_cursor: *mut sqlite3_vtab_cursor,
+    idx_num: c_int,
+    _idx_str: *const c_char,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) -> c_int {
+    let cursor = unsafe { &mut *p_cursor.cast::<Cursor>() };
+    let [text, separator] = unsafe { vtab::filter_arguments::<2>(idx_num, argv) };
+    let text = text.and_then(|value| unsafe { crate::value_text(value) });
+    let separator = match separator {
+        Some(value) => unsafe { crate::value_text(value) },
+        None => Some(DEFAULT_SEPARATOR),
+    };
+
+    cursor.row = 0;
+    cursor.tokens.clear();
+    if let (Some(text), Some(separator)) = (text, separator) {
+        let parse = |token: &str| crate::parse_uuid_str(token.trim());
+        if separator.is_empty() {
+            cursor.tokens.push(parse(text));
+        } else {
+            cursor.tokens.extend(text.split(separator).map(parse));
+        }
+        cursor.text.clear();
+        cursor.text.push_str(text);
+        cursor.separator.clear();
+        cursor.separator.push_str(separator);
+    }
+    SQLITE_OK
+}
+
+/// `xNext` callback advancing the cursor to the next token.
+unsafe extern "C" fn next(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    let cursor = unsafe { &mut *p_cursor.cast::<Cursor>() };
+    cursor.row += 1;
+    SQLITE_OK
+}
+
+/// `xEof` callback reporting whether all tokens were visited.
+unsafe extern "C" fn eof(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    let cursor = unsafe { &*p_cursor.cast::<Cursor>() };
+    c_int::from(cursor.row >= cursor.tokens.len())
+}
+
+/// `xColumn` callback returning a column of the current token, or the
+/// arguments of the function for the hidden columns.
+unsafe extern "C" fn column(
+    p_cursor: *mut sqlite3_vtab_cursor,
+    ctx: *mut sqlite3_context,
+    index: c_int,
+) -> c_int {
+    let cursor = unsafe { &*p_cursor.cast::<Cursor>() };
+    let token = cursor.tokens[cursor.row];
+    match (index, token) {
+        (COLUMN_VALUE, Some(u)) => unsafe { crate::result_uuid_text(ctx, &u) },
+        (COLUMN_OK, _) => unsafe { sqlite3_result_int(ctx, c_int::from(token.is_some())) },
+        (COLUMN_TEXT, _) => unsafe { crate::result_text(ctx, cursor.text.clone()) },
+        (COLUMN_SEPARATOR, _) => unsafe { crate::result_text(ctx, cursor.separator.clone()) },
+        _ => unsafe { sqlite3_result_null(ctx) },
+    }
+    SQLITE_OK
+}
+
+/// `xRowid` callback returning the 1-based position of the current token.
+unsafe extern "C" fn rowid(
+    p_cursor: *mut sqlite3_vtab_cursor,
+    p_rowid: *mut sqlite3_int64,
+) -> c_int {
+    let cursor = unsafe { &*p_cursor.cast::<Cursor>() };
+    unsafe {
+        *p_rowid = sqlite3_int64::try_from(cursor.row).unwrap() + 1;
+    }
+    SQLITE_OK
+}
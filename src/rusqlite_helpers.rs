@@ -0,0 +1,153 @@
+//! Helpers built on top of [`rusqlite`], available with the `rusqlite`
+//! feature.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use rusqlite::{Connection, ffi, types::Value};
+use uuid::Uuid;
+
+/// Quotes an SQL identifier, such as a table or column name.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Rewrites the UUIDs of a column as UUIDv7 derived from a timestamp column.
+///
+/// For every row of `table`, a new UUIDv7 embedding the Unix timestamp in
+/// milliseconds stored in `ts_col` is generated and written to `id_col`, so
+/// that a primary key originally populated with random UUIDv4 becomes sortable
+/// by creation time. The new id keeps the representation of the old one: BLOB
+/// ids are replaced by 16-byte BLOBs, and any other id by the canonical
+/// 36-character string. Rows whose id is `NULL` are left unchanged and not
+/// counted. All the updates are performed in a single transaction.
+///
+/// Foreign keys referencing the rewritten column are not updated: cascading the
+/// change (e.g. via `ON UPDATE CASCADE`) is left to the caller.
+///
+/// # Arguments
+/// * `conn` - The connection to the database holding `table`.
+/// * `table` - The name of the table to update.
+/// * `id_col` - The name of the UUID column to rewrite.
+/// * `ts_col` - The name of the column holding the Unix timestamp in
+///   milliseconds of each row.
+///
+/// # Returns
+/// * `rusqlite::Result<usize>` - The number of updated rows.
+///
+/// # Errors
+/// * Returns any error raised by SQLite while reading or updating the rows, in
+///   which case the transaction is rolled back.
+/// * Returns [`rusqlite::Error::IntegralValueOutOfRange`] if a timestamp is
+///   negative or above 2^48 - 1, the range of UUIDv7 timestamps.
+pub fn reassign_v7_from_timestamp(
+    conn: &mut Connection,
+    table: &str,
+    id_col: &str,
+    ts_col: &str,
+) -> rusqlite::Result<usize> {
+    let table = quote_identifier(table);
+    let id_col = quote_identifier(id_col);
+    let ts_col = quote_identifier(ts_col);
+
+    let tx = conn.transaction()?;
+    let mut updated = 0;
+    {
+        let rows = tx
+            .prepare(&format!("SELECT {id_col}, {ts_col} FROM {table} WHERE {id_col} IS NOT NULL"))?
+            .query_map([], |r| Ok((r.get::<_, Value>(0)?, r.get::<_, i64>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut update =
+            tx.prepare(&format!("UPDATE {table} SET {id_col} = ?1 WHERE {id_col} = ?2"))?;
+        for (id, ts) in rows {
+            let u = crate::generate::v7_at_millis(ts)
+                .ok_or(rusqlite::Error::IntegralValueOutOfRange(1, ts))?;
+            let new_id = match id {
+                Value::Blob(_) => Value::Blob(u.as_bytes().to_vec()),
+                _ => Value::Text(u.to_string()),
+            };
+            updated += update.execute((new_id, id))?;
+        }
+    }
+    tx.commit()?;
+    Ok(updated)
+}
+
+/// Returns whether an error is a violation of a `UNIQUE` or `PRIMARY KEY`
+/// constraint.
+fn is_unique_violation(error: &rusqlite::Error) -> bool {
+    matches!(
+        error,
+        rusqlite::Error::SqliteFailure(failure, _)
+            if failure.extended_code == ffi::SQLITE_CONSTRAINT_UNIQUE
+                || failure.extended_code == ffi::SQLITE_CONSTRAINT_PRIMARYKEY
+    )
+}
+
+/// Executes an `INSERT` with a fresh UUIDv4, retrying on collisions.
+///
+/// The statement `sql` is executed with a newly generated UUIDv4 bound to its
+/// first parameter (`?1`) as a canonical 36-character string; wrap it in
+/// `uuid_blob(?1)` to store it in a BLOB column. Whenever the insert violates a
+/// `UNIQUE` or `PRIMARY KEY` constraint, a new UUID is generated and the insert
+/// is attempted again, up to `max_retries` times.
+///
+/// # Arguments
+/// * `conn` - The connection to execute the statement on.
+/// * `sql` - The `INSERT` statement, binding the UUID as `?1`.
+/// * `max_retries` - The number of retries allowed after the first attempt.
+///
+/// # Returns
+/// * `rusqlite::Result<Uuid>` - The UUID that was successfully inserted.
+///
+/// # Errors
+/// * Returns the constraint violation of the last attempt if all the retries
+///   collided.
+/// * Returns any other error raised by SQLite immediately, without retrying.
+pub fn insert_with_uuid_retry(
+    conn: &Connection,
+    sql: &str,
+    max_retries: usize,
+) -> rusqlite::Result<Uuid> {
+    insert_with_uuid_retry_using(conn, sql, max_retries, crate::generate::new_v4)
+}
+
+/// Executes an `INSERT` with UUIDs drawn from `generate`, retrying on
+/// collisions.
+///
+/// This is the same as [`insert_with_uuid_retry`], but lets the caller choose
+/// how the UUIDs are generated, e.g. to use UUIDv7 or a seeded generator.
+///
+/// # Arguments
+/// * `conn` - The connection to execute the statement on.
+/// * `sql` - The `INSERT` statement, binding the UUID as `?1`.
+/// * `max_retries` - The number of retries allowed after the first attempt.
+/// * `generate` - The generator called to produce the UUID of each attempt.
+///
+/// # Returns
+/// * `rusqlite::Result<Uuid>` - The UUID that was successfully inserted.
+///
+/// # Errors
+/// * Returns the constraint violation of the last attempt if all the retries
+///   collided.
+/// * Returns any other error raised by SQLite immediately, without retrying.
+pub fn insert_with_uuid_retry_using(
+    conn: &Connection,
+    sql: &str,
+    max_retries: usize,
+    mut generate: impl FnMut() -> Uuid,
+) -> rusqlite::Result<Uuid> {
+    let mut statement = conn.prepare(sql)?;
+    let mut attempt = 0;
+    loop {
+        let u = generate();
+        match statement.execute([u.to_string()]) {
+            Ok(_) => return Ok(u),
+            Err(error) if is_unique_violation(&error) && attempt < max_retries => attempt += 1,
+            Err(error) => return Err(error),
+        }
+    }
+}
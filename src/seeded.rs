@@ -0,0 +1,148 @@
+//! Seeded generation of UUIDv4, installed by [`crate::register_with_seed`] so
+//! that `uuid()`, `uuid_blob()` and `uuid_series` yield reproducible values,
+//! e.g. in tests.
+//!
+//! Every connection gets its own generator, seeded when the connection is
+//! opened, so that all connections opened with the same seed produce the same
+//! sequence of UUIDs regardless of how their calls are interleaved.
+
+use alloc::rc::Rc;
+use core::{
+    cell::RefCell,
+    ffi::{CStr, c_char, c_int, c_void},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use rand_chacha::{
+    ChaCha8Rng,
+    rand_core::{RngCore, SeedableRng},
+};
+use uuid::{Builder, Uuid};
+
+use crate::ffi::{
+    SQLITE_INNOCUOUS, SQLITE_OK, SQLITE_UTF8, sqlite3, sqlite3_api_routines, sqlite3_context,
+    sqlite3_create_function_v2, sqlite3_create_module_v2, sqlite3_user_data, sqlite3_value,
+};
+
+/// Seed of the generators of the connections opened after the last call to
+/// [`crate::register_with_seed`].
+pub(crate) static SEED: AtomicU64 = AtomicU64::new(0);
+
+/// The generator of a connection, shared by its seeded `uuid()` and
+/// `uuid_blob()` functions as their user data, and by `uuid_series` as the
+/// client data of its module.
+pub(crate) type SeededRng = RefCell<ChaCha8Rng>;
+
+/// Generates a UUIDv4 from the generator of a connection.
+pub(crate) fn next_v4(rng: &SeededRng) -> Uuid {
+    let mut bytes = [0u8; 16];
+    rng.borrow_mut().fill_bytes(&mut bytes);
+    Builder::from_random_bytes(bytes).into_uuid()
+}
+
+/// Generates a UUIDv4 from the generator attached to the function being
+/// called.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the user data of `ctx`,
+/// which must be a [`SeededRng`] registered by [`sqlite3_uuid_init_seeded`].
+unsafe fn seeded_v4(ctx: *mut sqlite3_context) -> Uuid {
+    next_v4(unsafe { &*sqlite3_user_data(ctx).cast::<SeededRng>() })
+}
+
+/// Implementation of the seeded `uuid()` SQL function.
+unsafe extern "C" fn uuid_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        crate::result_uuid_text(ctx, &seeded_v4(ctx));
+    }
+}
+
+/// Implementation of the seeded `uuid_blob()` SQL function.
+unsafe extern "C" fn uuid_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) {
+    unsafe {
+        crate::result_uuid_blob(ctx, &seeded_v4(ctx));
+    }
+}
+
+/// Releases the reference to a [`SeededRng`] held by a seeded function or
+/// module.
+///
+/// # Safety
+/// This function is unsafe because it takes ownership of a raw pointer, which
+/// must have been produced by `Rc::into_raw` on a [`SeededRng`].
+unsafe extern "C" fn destroy_rng(p_app: *mut c_void) {
+    drop(unsafe { Rc::from_raw(p_app.cast::<SeededRng>()) });
+}
+
+/// Auto-extension entry point installed by [`crate::register_with_seed`].
+///
+/// Registers the functions like [`crate::sqlite3_uuid_init`], recording in
+/// their [`crate::Registration`] that the connection is seeded, then replaces
+/// `uuid()`, `uuid_blob()` and `uuid_series` with versions backed by a
+/// generator seeded with [`SEED`].
+///
+/// # Safety
+/// This function is unsafe because it interacts with raw SQLite pointers.
+/// It assumes `db` is a valid SQLite database connection.
+pub(crate) unsafe extern "C" fn sqlite3_uuid_init_seeded(
+    db: *mut sqlite3,
+    pz_err_msg: *mut *mut c_char,
+    p_api: *const sqlite3_api_routines,
+) -> c_int {
+    let set = crate::FunctionSet(crate::FUNCTION_SET.load(Ordering::Relaxed));
+    let registration = crate::Registration { set, strict: false, seeded: true };
+    let rc = unsafe { crate::init_connection(db, registration, pz_err_msg, p_api) };
+    if rc != SQLITE_OK {
+        return rc;
+    }
+
+    let rng: Rc<SeededRng> =
+        Rc::new(RefCell::new(ChaCha8Rng::seed_from_u64(SEED.load(Ordering::Relaxed))));
+    let functions: [(&CStr, crate::ScalarFunction); 2] =
+        [(c"uuid", uuid_func), (c"uuid_blob", uuid_blob_func)];
+    for (name, func) in functions {
+        // On failure, SQLite releases the reference through `destroy_rng`.
+        let rc = unsafe {
+            sqlite3_create_function_v2(
+                db,
+                name.as_ptr(),
+                0,
+                SQLITE_UTF8 | SQLITE_INNOCUOUS,
+                Rc::into_raw(Rc::clone(&rng)).cast_mut().cast::<c_void>(),
+                Some(func),
+                None,
+                None,
+                Some(destroy_rng),
+            )
+        };
+        if rc != SQLITE_OK {
+            let message =
+                alloc::format!("failed to register function {}/0", name.to_string_lossy());
+            unsafe { crate::set_error_message(pz_err_msg, &message) };
+            return rc;
+        }
+    }
+
+    // On failure, SQLite releases the reference through `destroy_rng`.
+    let rc = unsafe {
+        sqlite3_create_module_v2(
+            db,
+            c"uuid_series".as_ptr(),
+            &raw const crate::series::MODULE,
+            Rc::into_raw(rng).cast_mut().cast::<c_void>(),
+            Some(destroy_rng),
+        )
+    };
+    if rc != SQLITE_OK {
+        unsafe { crate::set_error_message(pz_err_msg, "failed to register module uuid_series") };
+    }
+    rc
+}
@@ -0,0 +1,259 @@
+//! Table-valued function `uuid_series(n, version)`, generating `n` fresh UUIDs
+//! in a single statement, e.g. to seed test data without a recursive CTE.
+//!
+//! After [`crate::register_with_seed`], the UUIDv4 of the series are drawn
+//! from the seeded generator of the connection, like those of `uuid()`.
+
+#[cfg(target_arch = "wasm32")]
+use alloc::rc::Rc;
+use core::{
+    ffi::{CStr, c_char, c_int, c_void},
+    ptr,
+};
+
+use crate::ffi::{
+    SQLITE_ERROR, SQLITE_OK, sqlite3, sqlite3_context, sqlite3_index_info, sqlite3_int64,
+    sqlite3_module, sqlite3_result_null, sqlite3_value, sqlite3_vtab, sqlite3_vtab_cursor,
+};
+use uuid::Uuid;
+
+#[cfg(target_arch = "wasm32")]
+use crate::seeded::SeededRng;
+use crate::{generate, vtab};
+
+/// Schema of the `uuid_series` virtual table.
+const SCHEMA: &CStr = c"CREATE TABLE x(uuid TEXT, n INTEGER HIDDEN, version INTEGER HIDDEN)";
+
+/// Index of the `uuid` column.
+const COLUMN_UUID: c_int = 0;
+/// Index of the hidden `n` column, the first argument of the function.
+const COLUMN_N: c_int = 1;
+
+/// Version of the UUIDs generated when the function is called without one.
+const DEFAULT_VERSION: i64 = 4;
+
+/// Virtual table of `uuid_series`.
+#[repr(C)]
+struct Table {
+    /// Base class, must come first.
+    base: sqlite3_vtab,
+    /// The generator of the connection, if seeded by
+    /// [`crate::register_with_seed`].
+    #[cfg(target_arch = "wasm32")]
+    rng: Option<Rc<SeededRng>>,
+}
+
+/// Generator of the UUIDs of a series.
+enum Generator {
+    /// A generator of the [`generate`] module.
+    Random(fn() -> Uuid),
+    /// UUIDv4 from the seeded generator of the connection.
+    #[cfg(target_arch = "wasm32")]
+    Seeded(Rc<SeededRng>),
+}
+
+impl Generator {
+    /// Generates the next UUID of the series.
+    fn generate(&self) -> Uuid {
+        match self {
+            Self::Random(generate) => generate(),
+            #[cfg(target_arch = "wasm32")]
+            Self::Seeded(rng) => crate::seeded::next_v4(rng),
+        }
+    }
+}
+
+/// Cursor generating the UUIDs of the series one row at a time.
+#[repr(C)]
+struct Cursor {
+    /// Base class, must come first.
+    base: sqlite3_vtab_cursor,
+    /// Generator of the UUIDs of the series.
+    generate: Generator,
+    /// Number of rows of the series.
+    len: usize,
+    /// Index of the current row.
+    row: usize,
+    /// The UUID of the current row.
+    current: Uuid,
+}
+
+/// The `sqlite3_module` implementing `uuid_series`.
+pub(crate) static MODULE: sqlite3_module = sqlite3_module {
+    xConnect: Some(connect),
+    xBestIndex: Some(best_index),
+    xDisconnect: Some(disconnect),
+    xOpen: Some(open),
+    xClose: Some(close),
+    xFilter: Some(filter),
+    xNext: Some(next),
+    xEof: Some(eof),
+    xColumn: Some(column),
+    xRowid: Some(rowid),
+    ..vtab::READ_ONLY_MODULE
+};
+
+/// `xConnect` callback declaring the schema of the virtual table.
+///
+/// The client data of the module is the seeded generator of the connection,
+/// if any, of which the table keeps a reference.
+unsafe extern "C" fn connect(
+    db: *mut sqlite3,
+    #[cfg_attr(not(target_arch = "wasm32"), allow(unused_variables))] p_aux: *mut c_void,
+    _argc: c_int,
+    _argv: *const *const c_char,
+    pp_vtab: *mut *mut sqlite3_vtab,
+    _pz_err: *mut *mut c_char,
+) -> c_int {
+    let table = Table {
+        base: vtab::base(),
+        #[cfg(target_arch = "wasm32")]
+        rng: (!p_aux.is_null()).then(|| unsafe {
+            let rng = p_aux.cast_const().cast::<SeededRng>();
+            Rc::increment_strong_count(rng);
+            Rc::from_raw(rng)
+        }),
+    };
+    unsafe { vtab::connect_table(db, SCHEMA, table, pp_vtab) }
+}
+
+/// `xDisconnect` callback releasing a [`Table`].
+unsafe extern "C" fn disconnect(p_vtab: *mut sqlite3_vtab) -> c_int {
+    unsafe { vtab::disconnect_table::<Table>(p_vtab) }
+}
+
+/// `xBestIndex` callback binding the `n` and `version` arguments.
+unsafe extern "C" fn best_index(
+    _p_vtab: *mut sqlite3_vtab,
+    p_info: *mut sqlite3_index_info,
+) -> c_int {
+    let info = unsafe { &mut *p_info };
+    match unsafe { vtab::bind_arguments(info, COLUMN_N, 2) } {
+        Ok(mask) => {
+            info.idxNum = mask;
+            SQLITE_OK
+        }
+        Err(rc) => rc,
+    }
+}
+
+/// `xOpen` callback allocating a new [`Cursor`].
+unsafe extern "C" fn open(
+    _p_vtab: *mut sqlite3_vtab,
+    pp_cursor: *mut *mut sqlite3_vtab_cursor,
+) -> c_int {
+    let cursor = Cursor {
+        base: sqlite3_vtab_cursor { pVtab: ptr::null_mut() },
+        generate: Generator::Random(generate::new_v4),
+        len: 0,
+        row: 0,
+        current: Uuid::nil(),
+    };
+    unsafe { vtab::open(cursor, pp_cursor) }
+}
+
+/// `xClose` callback releasing a [`Cursor`].
+unsafe extern "C" fn close(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    unsafe { vtab::close::<Cursor>(p_cursor) }
+}
+
+/// Returns the generator of UUIDv4 series of a [`Table`], seeded after
+/// [`crate::register_with_seed`].
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `p_vtab` pointer,
+/// which must have been allocated by [`connect`].
+#[cfg(target_arch = "wasm32")]
+unsafe fn v4_generator(p_vtab: *mut sqlite3_vtab) -> Generator {
+    match unsafe { &(*p_vtab.cast::<Table>()).rng } {
+        Some(rng) => Generator::Seeded(Rc::clone(rng)),
+        None => Generator::Random(generate::new_v4),
+    }
+}
+
+/// Returns the generator of UUIDv4 series of a [`Table`].
+///
+/// # Safety
+/// Safe to call with any pointer, for consistency with the `wasm32` version.
+#[cfg(not(target_arch = "wasm32"))]
+unsafe fn v4_generator(_p_vtab: *mut sqlite3_vtab) -> Generator {
+    Generator::Random(generate::new_v4)
+}
+
+/// `xFilter` callback rewinding the cursor to the start of a new series.
+///
+/// A `NULL`, missing or negative `n` yields no rows. The version defaults to
+/// 4, and any version other than 4 or 7 raises an error.
+unsafe extern "C" fn filter(
+    p_cursor: *mut sqlite3_vtab_cursor,
+    idx_num: c_int,
+    _idx_str: *const c_char,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) -> c_int {
+    let cursor = unsafe { &mut *p_cursor.cast::<Cursor>() };
+    let [n, version] = unsafe { vtab::filter_arguments::<2>(idx_num, argv) };
+    let version = match version {
+        Some(value) => unsafe { crate::value_int64(value) },
+        None => Some(DEFAULT_VERSION),
+    };
+    cursor.generate = match version {
+        Some(4) => unsafe { v4_generator(cursor.base.pVtab) },
+        Some(7) => Generator::Random(generate::now_v7),
+        _ => {
+            let message = "uuid_series: version must be 4 or 7";
+            unsafe { crate::set_error_message(&raw mut (*cursor.base.pVtab).zErrMsg, message) };
+            return SQLITE_ERROR;
+        }
+    };
+
+    let n = n.and_then(|value| unsafe { crate::value_int64(value) }).unwrap_or(0);
+    cursor.len = usize::try_from(n.max(0)).unwrap_or(usize::MAX);
+    cursor.row = 0;
+    if cursor.row < cursor.len {
+        cursor.current = cursor.generate.generate();
+    }
+    SQLITE_OK
+}
+
+/// `xNext` callback advancing the cursor to a newly generated UUID.
+unsafe extern "C" fn next(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    let cursor = unsafe { &mut *p_cursor.cast::<Cursor>() };
+    cursor.row += 1;
+    if cursor.row < cursor.len {
+        cursor.current = cursor.generate.generate();
+    }
+    SQLITE_OK
+}
+
+/// `xEof` callback reporting whether the whole series was generated.
+unsafe extern "C" fn eof(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    let cursor = unsafe { &*p_cursor.cast::<Cursor>() };
+    c_int::from(cursor.row >= cursor.len)
+}
+
+/// `xColumn` callback returning the UUID of the current row.
+unsafe extern "C" fn column(
+    p_cursor: *mut sqlite3_vtab_cursor,
+    ctx: *mut sqlite3_context,
+    index: c_int,
+) -> c_int {
+    let cursor = unsafe { &*p_cursor.cast::<Cursor>() };
+    match index {
+        COLUMN_UUID => unsafe { crate::result_uuid_text(ctx, &cursor.current) },
+        _ => unsafe { sqlite3_result_null(ctx) },
+    }
+    SQLITE_OK
+}
+
+/// `xRowid` callback returning the 1-based position of the current row.
+unsafe extern "C" fn rowid(
+    p_cursor: *mut sqlite3_vtab_cursor,
+    p_rowid: *mut sqlite3_int64,
+) -> c_int {
+    let cursor = unsafe { &*p_cursor.cast::<Cursor>() };
+    unsafe {
+        *p_rowid = sqlite3_int64::try_from(cursor.row).unwrap() + 1;
+    }
+    SQLITE_OK
+}
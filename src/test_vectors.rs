@@ -0,0 +1,338 @@
+//! Table-valued function `uuid_test_vectors`, exposing the example UUIDs from
+//! the appendices of [RFC 9562](https://www.rfc-editor.org/rfc/rfc9562) so
+//! that integrations can verify their conformance directly from SQL.
+//!
+//! Lookups by UUID never depend on the query plan: the `canonical` column is
+//! declared with the `UUID` collating sequence, so that `=` compares its text
+//! forms by value wherever SQLite evaluates it, while `uuid_eq` is overloaded
+//! through `xFindFunction` so that the planner can also use it as an index on
+//! either UUID column. `xBestIndex` only consumes the constraints it answers
+//! exactly as SQLite would.
+
+use core::{
+    ffi::{CStr, c_char, c_int, c_void},
+    ptr, slice,
+};
+
+use crate::ffi::{
+    SQLITE_BLOB, SQLITE_INDEX_CONSTRAINT_EQ, SQLITE_INDEX_CONSTRAINT_FUNCTION, SQLITE_OK,
+    SQLITE_STATIC, SQLITE_TEXT, sqlite3, sqlite3_context, sqlite3_index_constraint,
+    sqlite3_index_info, sqlite3_int64, sqlite3_module, sqlite3_result_int, sqlite3_result_null,
+    sqlite3_result_text, sqlite3_value, sqlite3_value_type, sqlite3_vtab, sqlite3_vtab_collation,
+    sqlite3_vtab_cursor,
+};
+use uuid::{Uuid, uuid};
+
+use crate::vtab;
+
+/// An example UUID from RFC 9562.
+struct TestVector {
+    /// The version of the example UUID.
+    version: c_int,
+    /// The input the example UUID is derived from, if any.
+    input: Option<&'static str>,
+    /// The example UUID.
+    uuid: Uuid,
+}
+
+/// The example UUIDs of RFC 9562, Appendix A and B.
+const TEST_VECTORS: &[TestVector] = &[
+    TestVector {
+        version: 1,
+        input: Some("2022-02-22T14:22:22-05:00"),
+        uuid: uuid!("c232ab00-9414-11ec-b3c8-9f6bdeced846"),
+    },
+    TestVector {
+        version: 3,
+        input: Some("www.example.com"),
+        uuid: uuid!("5df41881-3aed-3515-88a7-2f4a814cf09e"),
+    },
+    TestVector { version: 4, input: None, uuid: uuid!("919108f7-52d1-4320-9bac-f847db4148a8") },
+    TestVector {
+        version: 5,
+        input: Some("www.example.com"),
+        uuid: uuid!("2ed6657d-e927-568b-95e1-2665a8aea6a2"),
+    },
+    TestVector {
+        version: 6,
+        input: Some("2022-02-22T14:22:22-05:00"),
+        uuid: uuid!("1ec9414c-232a-6b00-b3c8-9f6bdeced846"),
+    },
+    TestVector {
+        version: 7,
+        input: Some("2022-02-22T14:22:22-05:00"),
+        uuid: uuid!("017f22e2-79b0-7cc3-98c4-dc0c0c07398f"),
+    },
+    TestVector {
+        version: 8,
+        input: Some("2022-02-22T14:22:22-05:00"),
+        uuid: uuid!("2489e9ad-2ee2-8e00-8ec9-32d5f69181c0"),
+    },
+    TestVector {
+        version: 8,
+        input: Some("www.example.com"),
+        uuid: uuid!("5c146b14-3c52-8afd-938a-375d0df1fbf6"),
+    },
+];
+
+/// Schema of the `uuid_test_vectors` virtual table.
+const SCHEMA: &CStr =
+    c"CREATE TABLE x(version INTEGER, input TEXT, canonical TEXT COLLATE UUID, blob BLOB)";
+
+/// Index of the `version` column.
+const COLUMN_VERSION: c_int = 0;
+/// Index of the `input` column.
+const COLUMN_INPUT: c_int = 1;
+/// Index of the `canonical` column.
+const COLUMN_CANONICAL: c_int = 2;
+/// Index of the `blob` column.
+const COLUMN_BLOB: c_int = 3;
+
+/// Constraint operator of `uuid_eq` calls overloaded by [`find_function`].
+const CONSTRAINT_UUID_EQ: c_int = SQLITE_INDEX_CONSTRAINT_FUNCTION;
+
+/// `idxNum` of the query plan filtering on a `uuid_eq` constraint, matching
+/// any representation of the UUID.
+const PLAN_UUID_EQ: c_int = 1;
+/// `idxNum` of the query plan filtering on `canonical = X` under the `UUID`
+/// collating sequence, matching any text form of the UUID.
+const PLAN_CANONICAL_EQ: c_int = 2;
+/// `idxNum` of the query plan filtering on `blob = X`, matching the 16 bytes
+/// of the UUID only.
+const PLAN_BLOB_EQ: c_int = 3;
+
+/// Cursor iterating over [`TEST_VECTORS`].
+#[repr(C)]
+struct Cursor {
+    /// Base class, must come first.
+    base: sqlite3_vtab_cursor,
+    /// Index of the current row in [`TEST_VECTORS`].
+    row: usize,
+    /// Whether the rows are filtered on an equality constraint.
+    filtered: bool,
+    /// The UUID the rows are filtered on, `None` if the constraint value is
+    /// not a valid UUID and therefore matches no row.
+    target: Option<Uuid>,
+}
+
+impl Cursor {
+    /// Skips the rows, starting from the current one, not matching the target.
+    fn skip_mismatches(&mut self) {
+        if self.filtered {
+            while TEST_VECTORS.get(self.row).is_some_and(|vector| Some(vector.uuid) != self.target)
+            {
+                self.row += 1;
+            }
+        }
+    }
+}
+
+/// Returns the query plan answering a constraint, if any.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `p_info` pointer,
+/// which must be the index information provided to `xBestIndex`, of which
+/// `constraint` is the constraint at `index`.
+unsafe fn constraint_plan(
+    p_info: *mut sqlite3_index_info,
+    index: usize,
+    constraint: &sqlite3_index_constraint,
+) -> Option<c_int> {
+    if constraint.usable == 0 {
+        return None;
+    }
+    match (constraint.iColumn, c_int::from(constraint.op)) {
+        (COLUMN_CANONICAL | COLUMN_BLOB, CONSTRAINT_UUID_EQ) => Some(PLAN_UUID_EQ),
+        (COLUMN_BLOB, SQLITE_INDEX_CONSTRAINT_EQ) => Some(PLAN_BLOB_EQ),
+        (COLUMN_CANONICAL, SQLITE_INDEX_CONSTRAINT_EQ) => {
+            // An explicit `COLLATE` clause overrides the one of the column.
+            let collation =
+                unsafe { sqlite3_vtab_collation(p_info, c_int::try_from(index).unwrap()) };
+            let uuid_collation = !collation.is_null()
+                && unsafe { CStr::from_ptr(collation) }
+                    .to_bytes()
+                    .eq_ignore_ascii_case(crate::UUID_COLLATION.to_bytes());
+            uuid_collation.then_some(PLAN_CANONICAL_EQ)
+        }
+        _ => None,
+    }
+}
+
+/// The `sqlite3_module` implementing `uuid_test_vectors`.
+pub(crate) static MODULE: sqlite3_module = sqlite3_module {
+    xConnect: Some(connect),
+    xBestIndex: Some(best_index),
+    xOpen: Some(open),
+    xClose: Some(close),
+    xFilter: Some(filter),
+    xNext: Some(next),
+    xEof: Some(eof),
+    xColumn: Some(column),
+    xRowid: Some(rowid),
+    xFindFunction: Some(find_function),
+    ..vtab::READ_ONLY_MODULE
+};
+
+/// `xConnect` callback declaring the schema of the virtual table.
+unsafe extern "C" fn connect(
+    db: *mut sqlite3,
+    _p_aux: *mut c_void,
+    _argc: c_int,
+    _argv: *const *const c_char,
+    pp_vtab: *mut *mut sqlite3_vtab,
+    _pz_err: *mut *mut c_char,
+) -> c_int {
+    unsafe { vtab::connect(db, SCHEMA, pp_vtab) }
+}
+
+/// `xFindFunction` callback overloading `uuid_eq(column, X)`, so that the
+/// planner can pass it to [`best_index`] as a constraint.
+///
+/// The overload is `uuid_eq` itself, so the result of a call is the same
+/// whether or not the constraint is consumed by the query plan.
+unsafe extern "C" fn find_function(
+    _p_vtab: *mut sqlite3_vtab,
+    n_arg: c_int,
+    z_name: *const c_char,
+    px_func: *mut Option<crate::ScalarFunction>,
+    _pp_arg: *mut *mut c_void,
+) -> c_int {
+    let name = unsafe { CStr::from_ptr(z_name) };
+    if n_arg != 2 || !name.to_bytes().eq_ignore_ascii_case(b"uuid_eq") {
+        return 0;
+    }
+    unsafe {
+        *px_func = Some(crate::uuid_eq_func);
+    }
+    CONSTRAINT_UUID_EQ
+}
+
+/// `xBestIndex` callback consuming a UUID lookup on the `canonical` or `blob`
+/// column, see [`constraint_plan`], falling back to a full scan otherwise.
+unsafe extern "C" fn best_index(
+    _p_vtab: *mut sqlite3_vtab,
+    p_info: *mut sqlite3_index_info,
+) -> c_int {
+    let info = unsafe { &mut *p_info };
+    let n_constraints = usize::try_from(info.nConstraint).unwrap_or(0);
+    if n_constraints > 0 {
+        let constraints = unsafe { slice::from_raw_parts(info.aConstraint, n_constraints) };
+        let usages = unsafe { slice::from_raw_parts_mut(info.aConstraintUsage, n_constraints) };
+        if let Some((position, plan)) =
+            constraints.iter().enumerate().find_map(|(position, constraint)| {
+                unsafe { constraint_plan(p_info, position, constraint) }
+                    .map(|plan| (position, plan))
+            })
+        {
+            usages[position].argvIndex = 1;
+            usages[position].omit = 1;
+            info.idxNum = plan;
+            info.estimatedCost = 1.0;
+            info.estimatedRows = 1;
+            return SQLITE_OK;
+        }
+    }
+    #[allow(clippy::cast_precision_loss)]
+    {
+        info.estimatedCost = TEST_VECTORS.len() as f64;
+    }
+    info.estimatedRows = sqlite3_int64::try_from(TEST_VECTORS.len()).unwrap();
+    SQLITE_OK
+}
+
+/// `xOpen` callback allocating a new [`Cursor`].
+unsafe extern "C" fn open(
+    _p_vtab: *mut sqlite3_vtab,
+    pp_cursor: *mut *mut sqlite3_vtab_cursor,
+) -> c_int {
+    let cursor = Cursor {
+        base: sqlite3_vtab_cursor { pVtab: ptr::null_mut() },
+        row: 0,
+        filtered: false,
+        target: None,
+    };
+    unsafe { vtab::open(cursor, pp_cursor) }
+}
+
+/// `xClose` callback releasing a [`Cursor`].
+unsafe extern "C" fn close(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    unsafe { vtab::close::<Cursor>(p_cursor) }
+}
+
+/// `xFilter` callback rewinding the cursor to the first matching test vector.
+unsafe extern "C" fn filter(
+    p_cursor: *mut sqlite3_vtab_cursor,
+    idx_num: c_int,
+    _idx_str: *const c_char,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) -> c_int {
+    let cursor = unsafe { &mut *p_cursor.cast::<Cursor>() };
+    cursor.row = 0;
+    cursor.filtered = matches!(idx_num, PLAN_UUID_EQ | PLAN_CANONICAL_EQ | PLAN_BLOB_EQ);
+    // Like SQLite, `=` never matches a TEXT against a BLOB.
+    let value_type = if cursor.filtered { unsafe { sqlite3_value_type(*argv) } } else { 0 };
+    cursor.target = match (idx_num, value_type) {
+        (PLAN_UUID_EQ, _) | (PLAN_CANONICAL_EQ, SQLITE_TEXT) | (PLAN_BLOB_EQ, SQLITE_BLOB) => unsafe {
+            crate::parse_uuid_arg(argv, 0)
+        },
+        _ => None,
+    };
+    cursor.skip_mismatches();
+    SQLITE_OK
+}
+
+/// `xNext` callback advancing the cursor to the next matching test vector.
+unsafe extern "C" fn next(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    let cursor = unsafe { &mut *p_cursor.cast::<Cursor>() };
+    cursor.row += 1;
+    cursor.skip_mismatches();
+    SQLITE_OK
+}
+
+/// `xEof` callback reporting whether all test vectors were visited.
+unsafe extern "C" fn eof(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    let cursor = unsafe { &*p_cursor.cast::<Cursor>() };
+    c_int::from(cursor.row >= TEST_VECTORS.len())
+}
+
+/// `xColumn` callback returning a column of the current test vector.
+unsafe extern "C" fn column(
+    p_cursor: *mut sqlite3_vtab_cursor,
+    ctx: *mut sqlite3_context,
+    index: c_int,
+) -> c_int {
+    let cursor = unsafe { &*p_cursor.cast::<Cursor>() };
+    let vector = &TEST_VECTORS[cursor.row];
+    match index {
+        COLUMN_VERSION => unsafe { sqlite3_result_int(ctx, vector.version) },
+        COLUMN_INPUT => match vector.input {
+            // The inputs are `'static`, so SQLite can use them without a copy.
+            Some(input) => unsafe {
+                sqlite3_result_text(
+                    ctx,
+                    input.as_ptr().cast::<c_char>(),
+                    c_int::try_from(input.len()).unwrap(),
+                    SQLITE_STATIC(),
+                );
+            },
+            None => unsafe { sqlite3_result_null(ctx) },
+        },
+        COLUMN_CANONICAL => unsafe { crate::result_uuid_text(ctx, &vector.uuid) },
+        COLUMN_BLOB => unsafe { crate::result_uuid_blob(ctx, &vector.uuid) },
+        _ => unsafe { sqlite3_result_null(ctx) },
+    }
+    SQLITE_OK
+}
+
+/// `xRowid` callback returning the 1-based index of the current test vector.
+unsafe extern "C" fn rowid(
+    p_cursor: *mut sqlite3_vtab_cursor,
+    p_rowid: *mut sqlite3_int64,
+) -> c_int {
+    let cursor = unsafe { &*p_cursor.cast::<Cursor>() };
+    unsafe {
+        *p_rowid = sqlite3_int64::try_from(cursor.row).unwrap() + 1;
+    }
+    SQLITE_OK
+}
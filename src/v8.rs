@@ -0,0 +1,94 @@
+//! SQL functions generating UUIDv8, whose layout is left to the application
+//! by [RFC 9562](https://www.rfc-editor.org/rfc/rfc9562#name-uuid-version-8),
+//! available with the `v8` feature.
+//!
+//! Besides the version and variant bits, a UUIDv8 holds three custom fields:
+//! `custom_a` (the first 48 bits), `custom_b` (12 bits) and `custom_c` (62
+//! bits). The functions of this module fill them from their argument:
+//! - An INTEGER between 0 and 2^48 - 1, such as a shard id, is stored
+//!   big-endian in `custom_a`, so that it occupies the high bits of the UUID,
+//!   while `custom_b` and `custom_c` are random.
+//! - A 16-byte BLOB provides all the bits, of which the version and variant
+//!   bits are overwritten.
+
+use core::ffi::c_int;
+
+use crate::ffi::{
+    SQLITE_BLOB, SQLITE_INTEGER, sqlite3_context, sqlite3_result_null, sqlite3_value,
+    sqlite3_value_type,
+};
+use uuid::Uuid;
+
+use crate::generate;
+
+/// Largest INTEGER accepted by the `uuid8` functions, which fills the 48-bit
+/// `custom_a` field.
+const MAX_CUSTOM_A: i64 = 0xFFFF_FFFF_FFFF;
+
+/// Helper function to build a UUIDv8 from the argument of the `uuid8`
+/// functions, as described in the [module documentation](self).
+///
+/// # Arguments
+/// * `arg` - The argument of the function.
+///
+/// # Returns
+/// * `Option<Uuid>` - The UUIDv8, or `None` if the argument is neither an
+///   INTEGER in range nor a 16-byte BLOB.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `arg` pointer.
+unsafe fn v8_from_arg(arg: *mut sqlite3_value) -> Option<Uuid> {
+    let bytes = match unsafe { sqlite3_value_type(arg) } {
+        SQLITE_INTEGER => {
+            let custom_a = unsafe { crate::value_int64(arg) }
+                .filter(|custom_a| (0..=MAX_CUSTOM_A).contains(custom_a))?;
+            let mut bytes = generate::new_v4().into_bytes();
+            bytes[..6].copy_from_slice(&custom_a.to_be_bytes()[2..]);
+            bytes
+        }
+        SQLITE_BLOB => unsafe { crate::value_blob(arg) }?.try_into().ok()?,
+        _ => return None,
+    };
+    // Overwrites the version and variant bits.
+    Some(Uuid::new_v8(bytes))
+}
+
+/// Implementation of the `uuid8(X)` SQL function.
+///
+/// Returns the UUIDv8 built from X, see the [module documentation](self), as
+/// a canonical 36-character string, or `NULL` if X is neither an INTEGER
+/// between 0 and 2^48 - 1 nor a 16-byte BLOB.
+pub(crate) unsafe extern "C" fn uuid8_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { v8_from_arg(*argv) } {
+        unsafe {
+            crate::result_uuid_text(ctx, &u);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
+
+/// Implementation of the `uuid8_blob(X)` SQL function.
+///
+/// Same as `uuid8`, but returns the UUID as a 16-byte BLOB.
+pub(crate) unsafe extern "C" fn uuid8_blob_func(
+    ctx: *mut sqlite3_context,
+    _argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    if let Some(u) = unsafe { v8_from_arg(*argv) } {
+        unsafe {
+            crate::result_uuid_blob(ctx, &u);
+        }
+    } else {
+        unsafe {
+            sqlite3_result_null(ctx);
+        }
+    }
+}
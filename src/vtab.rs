@@ -0,0 +1,228 @@
+//! Shared plumbing for the table-valued functions provided by the extension.
+//!
+//! Every table-valued function is implemented as an eponymous-only virtual
+//! table: it has no `xCreate` callback, so it can only be used through its
+//! name (e.g. `SELECT * FROM uuid_test_vectors`) and never via `CREATE VIRTUAL
+//! TABLE`.
+
+use alloc::boxed::Box;
+use core::{
+    ffi::{CStr, c_int},
+    ptr, slice,
+};
+
+use crate::ffi::{
+    SQLITE_CONSTRAINT, SQLITE_INDEX_CONSTRAINT_EQ, SQLITE_OK, sqlite3, sqlite3_declare_vtab,
+    sqlite3_index_info, sqlite3_module, sqlite3_value, sqlite3_vtab, sqlite3_vtab_cursor,
+};
+
+/// Returns an empty `sqlite3_vtab`, whose fields are filled in by SQLite.
+pub(crate) const fn base() -> sqlite3_vtab {
+    sqlite3_vtab { pModule: ptr::null(), nRef: 0, zErrMsg: ptr::null_mut() }
+}
+
+/// Declares the schema of a virtual table and allocates its `sqlite3_vtab`.
+///
+/// # Arguments
+/// * `db` - The SQLite database connection.
+/// * `schema` - The `CREATE TABLE` statement describing the virtual table.
+/// * `pp_vtab` - Output pointer receiving the newly allocated virtual table.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw SQLite pointers. It
+/// must only be called from an `xConnect` callback, with the arguments that
+/// SQLite provided to it.
+pub(crate) unsafe fn connect(
+    db: *mut sqlite3,
+    schema: &CStr,
+    pp_vtab: *mut *mut sqlite3_vtab,
+) -> c_int {
+    unsafe { connect_table(db, schema, base(), pp_vtab) }
+}
+
+/// Same as [`connect`], for a virtual table holding state of its own.
+///
+/// The table type must be `#[repr(C)]` and start with a `sqlite3_vtab`, so
+/// that SQLite can treat it as its base type.
+///
+/// # Safety
+/// This function is unsafe for the same reasons as [`connect`].
+pub(crate) unsafe fn connect_table<T>(
+    db: *mut sqlite3,
+    schema: &CStr,
+    table: T,
+    pp_vtab: *mut *mut sqlite3_vtab,
+) -> c_int {
+    let rc = unsafe { sqlite3_declare_vtab(db, schema.as_ptr()) };
+    if rc != SQLITE_OK {
+        return rc;
+    }
+    unsafe {
+        *pp_vtab = Box::into_raw(Box::new(table)).cast::<sqlite3_vtab>();
+    }
+    SQLITE_OK
+}
+
+/// `xDisconnect` callback releasing a virtual table allocated by [`connect`].
+///
+/// # Safety
+/// This function is unsafe because it takes ownership of a raw pointer, which
+/// must have been produced by [`connect`].
+pub(crate) unsafe extern "C" fn disconnect(p_vtab: *mut sqlite3_vtab) -> c_int {
+    unsafe { disconnect_table::<sqlite3_vtab>(p_vtab) }
+}
+
+/// Releases a virtual table previously allocated by [`connect_table`].
+///
+/// # Safety
+/// This function is unsafe because it takes ownership of a raw pointer, which
+/// must have been produced by [`connect_table`] with the same table type.
+pub(crate) unsafe fn disconnect_table<T>(p_vtab: *mut sqlite3_vtab) -> c_int {
+    drop(unsafe { Box::from_raw(p_vtab.cast::<T>()) });
+    SQLITE_OK
+}
+
+/// Moves a cursor onto the heap and hands it over to SQLite.
+///
+/// The cursor type must be `#[repr(C)]` and start with a
+/// `sqlite3_vtab_cursor`, so that SQLite can treat it as its base type.
+///
+/// # Safety
+/// This function is unsafe because it writes through the raw `pp_cursor`
+/// pointer provided by SQLite to an `xOpen` callback.
+pub(crate) unsafe fn open<C>(cursor: C, pp_cursor: *mut *mut sqlite3_vtab_cursor) -> c_int {
+    unsafe {
+        *pp_cursor = Box::into_raw(Box::new(cursor)).cast::<sqlite3_vtab_cursor>();
+    }
+    SQLITE_OK
+}
+
+/// Releases a cursor previously handed over to SQLite by [`open`].
+///
+/// # Safety
+/// This function is unsafe because it takes ownership of a raw pointer, which
+/// must have been produced by [`open`] with the same cursor type.
+pub(crate) unsafe fn close<C>(p_cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    drop(unsafe { Box::from_raw(p_cursor.cast::<C>()) });
+    SQLITE_OK
+}
+
+/// Binds the arguments of a table-valued function in an `xBestIndex` callback.
+///
+/// The arguments of a table-valued function are declared as consecutive
+/// `HIDDEN` columns of its virtual table, and SQLite passes them as equality
+/// constraints on those columns. Each usable constraint is forwarded to
+/// `xFilter` in argument order and omitted from SQLite's own checks.
+///
+/// # Arguments
+/// * `info` - The index information provided to `xBestIndex`.
+/// * `first_argument` - Index of the column of the first argument.
+/// * `n_arguments` - Number of arguments accepted by the function.
+///
+/// # Returns
+/// * `Ok(c_int)` - A bitmask of the bound arguments, where bit `i` is set if
+///   the `i`-th argument was provided, to be used as the `idxNum`.
+/// * `Err(SQLITE_CONSTRAINT)` - If an argument is provided but not usable in
+///   this query plan, telling SQLite to try a different one.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw constraint arrays
+/// of `info`, which must be the index information provided by SQLite.
+pub(crate) unsafe fn bind_arguments(
+    info: &mut sqlite3_index_info,
+    first_argument: c_int,
+    n_arguments: c_int,
+) -> Result<c_int, c_int> {
+    let n_constraints = usize::try_from(info.nConstraint).unwrap_or(0);
+    if n_constraints == 0 {
+        return Ok(0);
+    }
+    let constraints = unsafe { slice::from_raw_parts(info.aConstraint, n_constraints) };
+    let usages = unsafe { slice::from_raw_parts_mut(info.aConstraintUsage, n_constraints) };
+
+    let mut mask: c_int = 0;
+    for argument in 0..n_arguments {
+        let column = first_argument + argument;
+        let Some(position) = constraints.iter().position(|constraint| {
+            constraint.iColumn == column && c_int::from(constraint.op) == SQLITE_INDEX_CONSTRAINT_EQ
+        }) else {
+            continue;
+        };
+        if constraints[position].usable == 0 {
+            return Err(SQLITE_CONSTRAINT);
+        }
+        mask |= 1 << argument;
+        usages[position].argvIndex = c_int::try_from(mask.count_ones()).unwrap();
+        usages[position].omit = 1;
+    }
+    Ok(mask)
+}
+
+/// Retrieves the arguments bound by [`bind_arguments`] in an `xFilter`
+/// callback.
+///
+/// # Arguments
+/// * `idx_num` - The bitmask returned by [`bind_arguments`].
+/// * `argv` - The values provided by SQLite to `xFilter`.
+///
+/// # Returns
+/// * `[Option<*mut sqlite3_value>; N]` - The value of each argument, or `None`
+///   for the arguments which were not provided.
+///
+/// # Safety
+/// This function is unsafe because it dereferences the raw `argv` pointer,
+/// which must hold one value for each bit set in `idx_num`.
+pub(crate) unsafe fn filter_arguments<const N: usize>(
+    idx_num: c_int,
+    argv: *mut *mut sqlite3_value,
+) -> [Option<*mut sqlite3_value>; N] {
+    let mut arguments = [None; N];
+    let mut next = 0;
+    for (argument, value) in arguments.iter_mut().enumerate() {
+        if idx_num & (1 << argument) != 0 {
+            *value = Some(unsafe { *argv.add(next) });
+            next += 1;
+        }
+    }
+    arguments
+}
+
+/// A read-only eponymous virtual table module with all optional callbacks
+/// left unset, to be completed by the individual table-valued functions.
+pub(crate) const READ_ONLY_MODULE: sqlite3_module = sqlite3_module {
+    iVersion: 0,
+    xCreate: None,
+    xConnect: None,
+    xBestIndex: None,
+    xDisconnect: Some(disconnect),
+    xDestroy: None,
+    xOpen: None,
+    xClose: None,
+    xFilter: None,
+    xNext: None,
+    xEof: None,
+    xColumn: None,
+    xRowid: None,
+    xUpdate: None,
+    xBegin: None,
+    xSync: None,
+    xCommit: None,
+    xRollback: None,
+    xFindFunction: None,
+    xRename: None,
+    xSavepoint: None,
+    xRelease: None,
+    xRollbackTo: None,
+    xShadowName: None,
+    // Not part of the SQLite 3.34 API targeted by native loadable extensions.
+    #[cfg(target_arch = "wasm32")]
+    xIntegrity: None,
+};
+
+/// Description of a table-valued function registered by the extension.
+pub(crate) struct TableFunction {
+    /// Name of the table-valued function as seen from SQL.
+    pub(crate) name: &'static CStr,
+    /// Virtual table module implementing the function.
+    pub(crate) module: &'static sqlite3_module,
+}
@@ -0,0 +1,4 @@
+//! Crate testing `sqlite-wasm-uuid-rs` as a native loadable extension.
+//!
+//! The tests build the crate as a `cdylib` with the `loadable-ext` feature,
+//! then load it into a regular SQLite connection via `load_extension`.
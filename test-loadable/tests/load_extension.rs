@@ -0,0 +1,78 @@
+//! Tests loading `sqlite-wasm-uuid-rs` as a native loadable extension.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+};
+
+use rusqlite::Connection;
+
+/// Builds the extension as a `cdylib` and returns the path of the library.
+fn extension_path() -> &'static Path {
+    static PATH: OnceLock<PathBuf> = OnceLock::new();
+    PATH.get_or_init(|| {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let target_dir = manifest_dir.join("target").join("extension");
+        let status = Command::new(env!("CARGO"))
+            .arg("build")
+            .arg("--manifest-path")
+            .arg(manifest_dir.join("..").join("Cargo.toml"))
+            .args(["--lib", "--features", "loadable-ext", "--target-dir"])
+            .arg(&target_dir)
+            .status()
+            .expect("Failed to run cargo");
+        assert!(status.success(), "Failed to build the loadable extension");
+        target_dir.join("debug").join(format!(
+            "{}sqlite_wasm_uuid_rs{}",
+            env::consts::DLL_PREFIX,
+            env::consts::DLL_SUFFIX
+        ))
+    })
+}
+
+/// Opens an in-memory connection with the extension loaded via `entry_point`.
+fn open_with_extension(entry_point: &str) -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    unsafe {
+        conn.load_extension_enable().unwrap();
+        conn.load_extension(extension_path(), Some(entry_point))
+            .expect("Failed to load sqlite-wasm-uuid-rs");
+        conn.load_extension_disable().unwrap();
+    }
+    conn
+}
+
+/// Tests loading the extension and calling its functions.
+#[test]
+fn test_load_extension() {
+    let conn = open_with_extension("sqlite3_uuid_init");
+
+    let u1: String = conn.query_row("SELECT uuid()", [], |r| r.get(0)).unwrap();
+    let u2: String = conn.query_row("SELECT uuid()", [], |r| r.get(0)).unwrap();
+    assert_eq!(u1.len(), 36);
+    assert_ne!(u1, u2);
+
+    let version: i64 = conn.query_row("SELECT uuid_version(uuid7())", [], |r| r.get(0)).unwrap();
+    assert_eq!(version, 7);
+
+    let blob: Vec<u8> = conn.query_row("SELECT uuid_blob(?1)", [&u1], |r| r.get(0)).unwrap();
+    assert_eq!(blob.len(), 16);
+
+    let vectors: i64 =
+        conn.query_row("SELECT count(*) FROM uuid_test_vectors", [], |r| r.get(0)).unwrap();
+    assert_eq!(vectors, 8);
+
+    let probe: String = conn.query_row("SELECT uuid_probe()", [], |r| r.get(0)).unwrap();
+    assert!(probe.contains(r#""rng":"getrandom""#), "unexpected probe {probe}");
+}
+
+/// Tests loading the extension through its strict mode entry point.
+#[test]
+fn test_load_extension_strict() {
+    let conn = open_with_extension("sqlite3_uuid_init_strict");
+
+    let err = conn.query_row("SELECT uuid_str('not-a-uuid')", [], |r| r.get::<_, String>(0));
+    assert!(err.unwrap_err().to_string().contains("invalid UUID argument"));
+}
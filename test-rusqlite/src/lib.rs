@@ -1,6 +1,7 @@
 //! Crate illustrating usage of the `sqlite-wasm-uuid-rs` crate via `rusqlite`.
 extern crate alloc;
 use alloc::{
+    collections::BTreeSet,
     string::{String, ToString},
     vec::Vec,
 };
@@ -195,3 +196,2390 @@ fn test_uuid7_default() {
         assert_eq!(u.get_version_num(), 7);
     }
 }
+
+/// Tests the `uuid_sql_manifest` introspection function.
+#[wasm_bindgen_test]
+fn test_uuid_sql_manifest() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let manifest: String = conn.query_row("SELECT uuid_sql_manifest()", [], |r| r.get(0)).unwrap();
+    assert!(!manifest.is_empty());
+    assert!(manifest.contains("uuid7(0) non-deterministic"));
+    assert!(manifest.contains("uuid_str(1) deterministic"));
+    assert!(manifest.contains("uuid_sql_manifest(0) deterministic"));
+    assert!(manifest.contains("uuid_series table-valued"));
+    assert!(manifest.contains("uuid_test_vectors table-valued"));
+    assert!(manifest.ends_with("UUID collation\n"));
+}
+
+/// Tests the `uuid_test_vectors` table-valued function against RFC 9562.
+#[wasm_bindgen_test]
+fn test_uuid_test_vectors() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (canonical, blob): (String, Vec<u8>) = conn
+        .query_row("SELECT canonical, blob FROM uuid_test_vectors WHERE version = 7", [], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(canonical, "017f22e2-79b0-7cc3-98c4-dc0c0c07398f");
+    assert_eq!(
+        blob,
+        [
+            0x01, 0x7F, 0x22, 0xE2, 0x79, 0xB0, 0x7C, 0xC3, 0x98, 0xC4, 0xDC, 0x0C, 0x0C, 0x07,
+            0x39, 0x8F
+        ]
+    );
+
+    let rows: Vec<(i64, i64, String, Vec<u8>)> = conn
+        .prepare("SELECT version, uuid_version(blob), canonical, blob FROM uuid_test_vectors")
+        .unwrap()
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(rows.len(), 8);
+    for (version, detected_version, canonical, blob) in &rows {
+        assert_eq!(detected_version, version);
+        let u = Uuid::parse_str(canonical).unwrap();
+        assert_eq!(u.as_bytes().as_slice(), blob.as_slice());
+    }
+}
+
+/// Tests the `uuid_parse_many` table-valued function.
+#[wasm_bindgen_test]
+fn test_uuid_parse_many() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let list = "12345678-1234-1234-1234-123456789abc, not-a-uuid,\
+                00000000-0000-0000-0000-000000000000";
+    let rows: Vec<(Option<String>, bool)> = conn
+        .prepare("SELECT value, ok FROM uuid_parse_many(?1, ',')")
+        .unwrap()
+        .query_map([list], |r| Ok((r.get(0)?, r.get(1)?)))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            (Some("12345678-1234-1234-1234-123456789abc".to_string()), true),
+            (None, false),
+            (Some("00000000-0000-0000-0000-000000000000".to_string()), true),
+        ]
+    );
+
+    let (text, separator): (String, String) = conn
+        .query_row("SELECT text, separator FROM uuid_parse_many(?1, ';') LIMIT 1", [list], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(text, list);
+    assert_eq!(separator, ";");
+}
+
+/// Tests rewriting a UUIDv4 primary key as UUIDv7 via
+/// `reassign_v7_from_timestamp`.
+#[cfg(feature = "rusqlite-helpers")]
+#[wasm_bindgen_test]
+fn test_reassign_v7_from_timestamp() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let mut conn = Connection::open_in_memory().unwrap();
+
+    conn.execute("CREATE TABLE t(id TEXT PRIMARY KEY DEFAULT (uuid()), created_at INTEGER)", [])
+        .unwrap();
+    // Insert in an order which differs from the timestamp order.
+    for ts in [1_700_000_300_000_i64, 1_700_000_100_000, 1_700_000_200_000, 1_700_000_000_000] {
+        conn.execute("INSERT INTO t(created_at) VALUES (?1)", [ts]).unwrap();
+    }
+
+    let updated = sqlite_wasm_uuid_rs::rusqlite_helpers::reassign_v7_from_timestamp(
+        &mut conn,
+        "t",
+        "id",
+        "created_at",
+    )
+    .unwrap();
+    assert_eq!(updated, 4);
+
+    let rows: Vec<(String, i64)> = conn
+        .prepare("SELECT id, created_at FROM t ORDER BY id")
+        .unwrap()
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(rows.len(), 4);
+    for (id, _) in &rows {
+        assert_eq!(Uuid::parse_str(id).unwrap().get_version_num(), 7);
+    }
+    for i in 0..rows.len() - 1 {
+        assert!(rows[i].1 < rows[i + 1].1, "UUIDv7 not sorted by timestamp at index {}", i);
+    }
+
+    // Rows without an id are left unchanged and not counted.
+    conn.execute("INSERT INTO t(id, created_at) VALUES (NULL, 1700000400000)", []).unwrap();
+    let updated = sqlite_wasm_uuid_rs::rusqlite_helpers::reassign_v7_from_timestamp(
+        &mut conn,
+        "t",
+        "id",
+        "created_at",
+    )
+    .unwrap();
+    assert_eq!(updated, 4);
+    let nulls: i64 =
+        conn.query_row("SELECT count(*) FROM t WHERE id IS NULL", [], |r| r.get(0)).unwrap();
+    assert_eq!(nulls, 1);
+
+    // Timestamps beyond the 48 bits of UUIDv7 are rejected, rolling back.
+    let ids = |conn: &Connection| -> Vec<String> {
+        conn.prepare("SELECT id FROM t WHERE id IS NOT NULL ORDER BY rowid")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    };
+    let before = ids(&conn);
+    conn.execute("UPDATE t SET created_at = 1 << 48 WHERE created_at = 1700000000000", []).unwrap();
+    let error = sqlite_wasm_uuid_rs::rusqlite_helpers::reassign_v7_from_timestamp(
+        &mut conn,
+        "t",
+        "id",
+        "created_at",
+    )
+    .unwrap_err();
+    assert!(
+        matches!(error, rusqlite::Error::IntegralValueOutOfRange(1, ts) if ts == 1 << 48),
+        "unexpected error {error}"
+    );
+    assert_eq!(ids(&conn), before);
+}
+
+/// Tests the `uuid_checksum_text` and `uuid_verify_checksum_text` functions.
+#[wasm_bindgen_test]
+fn test_uuid_checksum_text() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    for _ in 0..100 {
+        let (checksummed, verified): (String, i64) = conn
+            .query_row(
+                "SELECT c, uuid_verify_checksum_text(c) FROM (SELECT uuid_checksum_text(uuid()) AS c)",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(checksummed.len(), 35);
+        assert_eq!(verified, 1);
+
+        for position in [0, 13, 31] {
+            let mut flipped = checksummed.clone().into_bytes();
+            flipped[position] = if flipped[position] == b'0' { b'1' } else { b'0' };
+            let flipped = String::from_utf8(flipped).unwrap();
+            let verified: i64 = conn
+                .query_row("SELECT uuid_verify_checksum_text(?1)", [&flipped], |r| r.get(0))
+                .unwrap();
+            assert_eq!(verified, 0, "flipped checksum {} still verifies", flipped);
+        }
+    }
+
+    let invalid: i64 =
+        conn.query_row("SELECT uuid_verify_checksum_text('not-a-uuid')", [], |r| r.get(0)).unwrap();
+    assert_eq!(invalid, 0);
+}
+
+/// Tests that `uuid_generate_monotonic_text` is strictly increasing.
+#[wasm_bindgen_test]
+fn test_uuid_generate_monotonic_text() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let ids: Vec<String> = conn
+        .prepare(
+            "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 100000)
+             SELECT uuid_generate_monotonic_text() FROM c",
+        )
+        .unwrap()
+        .query_map([], |r| r.get(0))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(ids.len(), 100000);
+
+    for i in 0..ids.len() - 1 {
+        assert!(
+            ids[i] < ids[i + 1],
+            "Monotonic text not strictly increasing at index {}: {} >= {}",
+            i,
+            ids[i],
+            ids[i + 1]
+        );
+    }
+    assert_eq!(Uuid::parse_str(&ids[0]).unwrap().get_version_num(), 7);
+}
+
+/// Tests the `uuid_probe` diagnostic function.
+#[wasm_bindgen_test]
+fn test_uuid_probe() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (valid, rng, clock, version): (i64, String, String, String) = conn
+        .query_row(
+            "SELECT json_valid(p), json_extract(p, '$.rng'), json_extract(p, '$.clock'),
+                    json_extract(p, '$.version')
+             FROM (SELECT uuid_probe() AS p)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(valid, 1);
+    assert_eq!(rng, "crypto.getRandomValues");
+    assert!(!clock.is_empty());
+    assert!(!version.is_empty());
+}
+
+/// Tests the `uuid_as_integer_blob` and `uuid_from_integer_blob` functions.
+#[wasm_bindgen_test]
+fn test_uuid_integer_blob() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let as_integer: Vec<u8> = conn
+        .query_row("SELECT uuid_as_integer_blob('12345678-1234-1234-1234-123456789abc')", [], |r| {
+            r.get(0)
+        })
+        .unwrap();
+    let as_blob: Vec<u8> = conn
+        .query_row("SELECT uuid_blob('12345678-1234-1234-1234-123456789abc')", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(as_integer, as_blob);
+
+    // A small UUID value whose two leading zero bytes were stripped.
+    let integer: Vec<u8> = (1..=14).collect();
+    let restored: Vec<u8> =
+        conn.query_row("SELECT uuid_from_integer_blob(?1)", [&integer], |r| r.get(0)).unwrap();
+    let mut expected = vec![0, 0];
+    expected.extend_from_slice(&integer);
+    assert_eq!(restored, expected);
+
+    let text: String =
+        conn.query_row("SELECT uuid_str(uuid_from_integer_blob(X'01'))", [], |r| r.get(0)).unwrap();
+    assert_eq!(text, "00000000-0000-0000-0000-000000000001");
+
+    let too_long: Option<Vec<u8>> =
+        conn.query_row("SELECT uuid_from_integer_blob(zeroblob(17))", [], |r| r.get(0)).unwrap();
+    assert_eq!(too_long, None);
+}
+
+/// Tests that `uuid_dedup_pair_key` is symmetric and distinguishes pairs.
+#[wasm_bindgen_test]
+fn test_uuid_dedup_pair_key() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let a = Uuid::new_v4().to_string();
+    let b = Uuid::new_v4();
+    let c = Uuid::new_v4().to_string();
+
+    // Mix TEXT and BLOB arguments: the key only depends on the UUID values.
+    let (ab, ba, ac): (String, String, String) = conn
+        .query_row(
+            "SELECT uuid_dedup_pair_key(?1, ?2), uuid_dedup_pair_key(?2, ?1),
+                    uuid_dedup_pair_key(?1, ?3)",
+            rusqlite::params![a, b.as_bytes().as_slice(), c],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(ab, ba);
+    assert_ne!(ab, ac);
+    assert_eq!(Uuid::parse_str(&ab).unwrap().get_version_num(), 5);
+
+    let invalid: Option<String> =
+        conn.query_row("SELECT uuid_dedup_pair_key(?1, 'not-a-uuid')", [&a], |r| r.get(0)).unwrap();
+    assert_eq!(invalid, None);
+}
+
+/// Tests the `uuid_version` function.
+#[wasm_bindgen_test]
+fn test_uuid_version() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let v7: i64 = conn.query_row("SELECT uuid_version(uuid7())", [], |r| r.get(0)).unwrap();
+    assert_eq!(v7, 7);
+    let v4: i64 = conn.query_row("SELECT uuid_version(uuid())", [], |r| r.get(0)).unwrap();
+    assert_eq!(v4, 4);
+    let v7_blob: i64 =
+        conn.query_row("SELECT uuid_version(uuid7_blob())", [], |r| r.get(0)).unwrap();
+    assert_eq!(v7_blob, 7);
+    let invalid: Option<i64> =
+        conn.query_row("SELECT uuid_version('not-a-uuid')", [], |r| r.get(0)).unwrap();
+    assert_eq!(invalid, None);
+}
+
+/// Tests that `insert_with_uuid_retry_using` retries on a colliding UUID.
+#[cfg(feature = "rusqlite-helpers")]
+#[wasm_bindgen_test]
+fn test_insert_with_uuid_retry() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute("CREATE TABLE t(id TEXT PRIMARY KEY, name TEXT)", []).unwrap();
+
+    let known = Uuid::new_v4();
+    conn.execute("INSERT INTO t(id, name) VALUES (?1, 'first')", [known.to_string()]).unwrap();
+
+    // The generator first replays the already inserted id, forcing a collision.
+    let mut replay = Some(known);
+    let inserted = sqlite_wasm_uuid_rs::rusqlite_helpers::insert_with_uuid_retry_using(
+        &conn,
+        "INSERT INTO t(id, name) VALUES (?1, 'second')",
+        3,
+        || replay.take().unwrap_or_else(Uuid::new_v4),
+    )
+    .unwrap();
+    assert_ne!(inserted, known);
+
+    let name: String = conn
+        .query_row("SELECT name FROM t WHERE id = ?1", [inserted.to_string()], |r| r.get(0))
+        .unwrap();
+    assert_eq!(name, "second");
+
+    // Without retries, the collision is reported.
+    let error = sqlite_wasm_uuid_rs::rusqlite_helpers::insert_with_uuid_retry_using(
+        &conn,
+        "INSERT INTO t(id, name) VALUES (?1, 'third')",
+        0,
+        || known,
+    )
+    .unwrap_err();
+    assert_eq!(error.sqlite_error_code(), Some(rusqlite::ErrorCode::ConstraintViolation));
+
+    let fresh = sqlite_wasm_uuid_rs::rusqlite_helpers::insert_with_uuid_retry(
+        &conn,
+        "INSERT INTO t(id, name) VALUES (?1, 'fourth')",
+        3,
+    )
+    .unwrap();
+    assert_eq!(fresh.get_version_num(), 4);
+}
+
+/// Tests the `uuid5` and `uuid5_blob` functions against the well-known
+/// `example.com` vector in the DNS namespace.
+#[wasm_bindgen_test]
+fn test_uuid5() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let namespace_dns = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+    let expected = "cfbff0d1-9375-5685-968c-48ce8b15ae17";
+
+    let text: String =
+        conn.query_row("SELECT uuid5(?1, 'example.com')", [namespace_dns], |r| r.get(0)).unwrap();
+    assert_eq!(text, expected);
+
+    let namespace_blob = Uuid::parse_str(namespace_dns).unwrap().as_bytes().to_vec();
+    let from_blob_namespace: String =
+        conn.query_row("SELECT uuid5(?1, 'example.com')", [&namespace_blob], |r| r.get(0)).unwrap();
+    assert_eq!(from_blob_namespace, expected);
+
+    let blob: Vec<u8> = conn
+        .query_row("SELECT uuid5_blob(?1, 'example.com')", [namespace_dns], |r| r.get(0))
+        .unwrap();
+    assert_eq!(blob, Uuid::parse_str(expected).unwrap().as_bytes().to_vec());
+
+    let invalid: Option<String> =
+        conn.query_row("SELECT uuid5('not-a-uuid', 'example.com')", [], |r| r.get(0)).unwrap();
+    assert_eq!(invalid, None);
+}
+
+/// Tests the `uuid3` and `uuid3_blob` functions against the `www.example.com`
+/// vector in the DNS namespace.
+#[wasm_bindgen_test]
+fn test_uuid3() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let namespace_dns = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+    let expected = "5df41881-3aed-3515-88a7-2f4a814cf09e";
+
+    let text: String = conn
+        .query_row("SELECT uuid3(?1, 'www.example.com')", [namespace_dns], |r| r.get(0))
+        .unwrap();
+    assert_eq!(text, expected);
+
+    let blob: Vec<u8> = conn
+        .query_row("SELECT uuid3_blob(?1, 'www.example.com')", [namespace_dns], |r| r.get(0))
+        .unwrap();
+    assert_eq!(blob, Uuid::parse_str(expected).unwrap().as_bytes().to_vec());
+
+    let invalid: Option<String> =
+        conn.query_row("SELECT uuid3(X'00', 'www.example.com')", [], |r| r.get(0)).unwrap();
+    assert_eq!(invalid, None);
+}
+
+/// Tests that `uuid_version_of_blob` matches `uuid_version` on BLOBs.
+#[wasm_bindgen_test]
+fn test_uuid_version_of_blob() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (v7, v7_full): (i64, i64) = conn
+        .query_row(
+            "SELECT uuid_version_of_blob(b), uuid_version(b) FROM (SELECT uuid7_blob() AS b)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(v7, 7);
+    assert_eq!(v7, v7_full);
+
+    let (v4, v4_full): (i64, i64) = conn
+        .query_row(
+            "SELECT uuid_version_of_blob(b), uuid_version(b) FROM (SELECT uuid_blob() AS b)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(v4, 4);
+    assert_eq!(v4, v4_full);
+
+    let (short, text): (Option<i64>, Option<i64>) = conn
+        .query_row("SELECT uuid_version_of_blob(X'0011'), uuid_version_of_blob(uuid())", [], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(short, None);
+    assert_eq!(text, None);
+}
+
+/// Tests that UUID lookups on `uuid_test_vectors` return the same rows
+/// whether the virtual table answers them or SQLite evaluates them row by row,
+/// as with an `OR` or when the virtual table is the outer loop of a join.
+#[wasm_bindgen_test]
+fn test_uuid_test_vectors_uuid_equality() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute("CREATE TABLE needle(id)", []).unwrap();
+
+    let v7 = "'017F22E2-79B0-7CC3-98C4-DC0C0C07398F'";
+    let cases = [
+        ("uuid_eq(v.blob, needle.id)", v7.to_owned(), vec![7]),
+        (
+            "uuid_eq(v.canonical, needle.id)",
+            "uuid_blob('5df41881-3aed-3515-88a7-2f4a814cf09e')".to_owned(),
+            vec![3],
+        ),
+        ("uuid_eq(v.blob, needle.id)", "'not-a-uuid'".to_owned(), vec![]),
+        ("v.canonical = needle.id", format!("'{{' || {v7} || '}}'"), vec![7]),
+        ("v.blob = needle.id", format!("uuid_blob({v7})"), vec![7]),
+        // Like any comparison of a BLOB with a TEXT, never true.
+        ("v.blob = needle.id", v7.to_owned(), vec![]),
+    ];
+    let shapes = [
+        "SELECT v.version FROM needle CROSS JOIN uuid_test_vectors AS v WHERE {}",
+        "SELECT v.version FROM needle CROSS JOIN uuid_test_vectors AS v WHERE {} OR v.version = 99",
+        "SELECT v.version FROM uuid_test_vectors AS v CROSS JOIN needle WHERE {}",
+    ];
+    for (predicate, needle, expected) in cases {
+        conn.execute("DELETE FROM needle", []).unwrap();
+        conn.execute(&format!("INSERT INTO needle VALUES ({needle})"), []).unwrap();
+        for shape in shapes {
+            let sql = shape.replace("{}", predicate);
+            let versions: Vec<i64> = conn
+                .prepare(&sql)
+                .unwrap()
+                .query_map([], |r| r.get(0))
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(versions, expected, "{sql} with {needle}");
+        }
+    }
+}
+
+/// Tests the `uuid_ns_*` namespace functions, also in combination with `uuid5`.
+#[wasm_bindgen_test]
+fn test_uuid_namespaces() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (dns, url, oid, x500): (String, String, String, String) = conn
+        .query_row("SELECT uuid_ns_dns(), uuid_ns_url(), uuid_ns_oid(), uuid_ns_x500()", [], |r| {
+            Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?))
+        })
+        .unwrap();
+    assert_eq!(dns, Uuid::NAMESPACE_DNS.to_string());
+    assert_eq!(url, Uuid::NAMESPACE_URL.to_string());
+    assert_eq!(oid, Uuid::NAMESPACE_OID.to_string());
+    assert_eq!(x500, Uuid::NAMESPACE_X500.to_string());
+
+    let (from_function, from_string): (String, String) = conn
+        .query_row(
+            "SELECT uuid5(uuid_ns_dns(), 'example.com'), \
+             uuid5('6ba7b810-9dad-11d1-80b4-00c04fd430c8', 'example.com')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(from_function, from_string);
+    assert_eq!(from_function, "cfbff0d1-9375-5685-968c-48ce8b15ae17");
+}
+
+/// Tests the `uuid_timestamp_resolution` function on timestamped and
+/// untimestamped versions.
+#[wasm_bindgen_test]
+fn test_uuid_timestamp_resolution() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let resolutions: Vec<(i64, Option<String>)> = conn
+        .prepare("SELECT version, uuid_timestamp_resolution(blob) FROM uuid_test_vectors")
+        .unwrap()
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    for (version, resolution) in resolutions {
+        let expected = match version {
+            1 | 6 => Some("100ns"),
+            7 => Some("1ms"),
+            _ => None,
+        };
+        assert_eq!(resolution.as_deref(), expected, "version {version}");
+    }
+
+    let (v1, v7, invalid): (String, String, Option<String>) = conn
+        .query_row(
+            "SELECT uuid_timestamp_resolution('c232ab00-9414-11ec-b3c8-9f6bdeced846'), \
+             uuid_timestamp_resolution(uuid7()), uuid_timestamp_resolution('not-a-uuid')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(v1, "100ns");
+    assert_eq!(v7, "1ms");
+    assert_eq!(invalid, None);
+}
+
+/// Tests the `uuid_nil` and `uuid_max` sentinel functions and their BLOB
+/// variants.
+#[wasm_bindgen_test]
+fn test_uuid_nil_and_max() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (nil, max): (String, String) = conn
+        .query_row("SELECT uuid_nil(), uuid_max()", [], |r| Ok((r.get(0)?, r.get(1)?)))
+        .unwrap();
+    assert_eq!(nil, "00000000-0000-0000-0000-000000000000");
+    assert_eq!(max, "ffffffff-ffff-ffff-ffff-ffffffffffff");
+
+    let (nil_blob, max_blob): (Vec<u8>, Vec<u8>) = conn
+        .query_row("SELECT uuid_nil_blob(), uuid_max_blob()", [], |r| Ok((r.get(0)?, r.get(1)?)))
+        .unwrap();
+    assert_eq!(nil_blob, [0u8; 16]);
+    assert_eq!(max_blob, [0xFF; 16]);
+}
+
+/// Tests that `uuid_v1_with_clock_seq` embeds the supplied node and clock
+/// sequence, as reported by `uuid_node` and `uuid_clock_seq`.
+#[wasm_bindgen_test]
+fn test_uuid_v1_with_clock_seq() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (version, clock_seq, node): (i64, i64, i64) = conn
+        .query_row(
+            "SELECT uuid_version(u), uuid_clock_seq(u), uuid_node(u) \
+             FROM (SELECT uuid_v1_with_clock_seq(0x123456789ABC, 0x1234) AS u)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(version, 1);
+    assert_eq!(clock_seq, 0x1234);
+    assert_eq!(node, 0x1234_5678_9ABC);
+
+    let (clock_seq, node): (i64, i64) = conn
+        .query_row(
+            "SELECT uuid_clock_seq(u), uuid_node(u) \
+             FROM (SELECT uuid_v1_with_clock_seq_blob(42, 7) AS u)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(clock_seq, 7);
+    assert_eq!(node, 42);
+
+    let (big_node, big_clock_seq, not_v1): (Option<String>, Option<String>, Option<i64>) = conn
+        .query_row(
+            "SELECT uuid_v1_with_clock_seq(0x1000000000000, 0), \
+             uuid_v1_with_clock_seq(0, 0x4000), uuid_node(uuid7())",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(big_node, None);
+    assert_eq!(big_clock_seq, None);
+    assert_eq!(not_v1, None);
+}
+
+/// Tests that `uuid7_timestamp` extracts the creation time of a row keyed by
+/// a `uuid7()` default.
+#[wasm_bindgen_test]
+fn test_uuid7_timestamp() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    conn.execute_batch(
+        "CREATE TABLE events (id TEXT PRIMARY KEY DEFAULT (uuid7()), name TEXT);
+         INSERT INTO events (name) VALUES ('created');",
+    )
+    .unwrap();
+
+    let (created, now): (i64, i64) = conn
+        .query_row(
+            "SELECT uuid7_timestamp(id), \
+             CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER) FROM events",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert!((now - created).abs() < 5000, "created {created} is not close to now {now}");
+
+    let from_blob: i64 = conn
+        .query_row("SELECT uuid7_timestamp(uuid_blob(id)) FROM events", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(from_blob, created);
+
+    let (vector, not_v7, invalid): (i64, Option<i64>, Option<i64>) = conn
+        .query_row(
+            "SELECT uuid7_timestamp('017f22e2-79b0-7cc3-98c4-dc0c0c07398f'), \
+             uuid7_timestamp(uuid()), uuid7_timestamp('not-a-uuid')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(vector, 0x017F_22E2_79B0);
+    assert_eq!(not_v7, None);
+    assert_eq!(invalid, None);
+}
+
+/// Tests that `uuid_hamming_bucket` groups UUIDs by their common prefix.
+#[wasm_bindgen_test]
+fn test_uuid_hamming_bucket() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (a, b, c): (i64, i64, i64) = conn
+        .query_row(
+            "SELECT uuid_hamming_bucket('0123abcd-89ab-4def-8123-456789abcdef', 16), \
+             uuid_hamming_bucket('0123ffff-0000-4000-8000-000000000000', 16), \
+             uuid_hamming_bucket('f123abcd-89ab-4def-8123-456789abcdef', 16)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(a, 0x0123);
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    let distinct: i64 = conn
+        .query_row(
+            "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 100) \
+             SELECT count(DISTINCT uuid_hamming_bucket(uuid(), 32)) FROM n",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert!(distinct > 90, "only {distinct} distinct buckets out of 100 random UUIDs");
+
+    let (zero, too_many, invalid): (Option<i64>, Option<i64>, Option<i64>) = conn
+        .query_row(
+            "SELECT uuid_hamming_bucket(uuid(), 0), uuid_hamming_bucket(uuid(), 53), \
+             uuid_hamming_bucket('not-a-uuid', 8)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(zero, None);
+    assert_eq!(too_many, None);
+    assert_eq!(invalid, None);
+}
+
+/// Tests that `uuid7_at` and `uuid7_at_blob` embed the supplied timestamp.
+#[wasm_bindgen_test]
+fn test_uuid7_at() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (first, second): (String, String) = conn
+        .query_row("SELECT uuid7_at(1000), uuid7_at(2000)", [], |r| Ok((r.get(0)?, r.get(1)?)))
+        .unwrap();
+    assert!(first < second, "{first} does not sort before {second}");
+
+    let (timestamp, blob_timestamp, version): (i64, i64, i64) = conn
+        .query_row(
+            "SELECT uuid7_timestamp(uuid7_at(1700000000123)), \
+             uuid7_timestamp(uuid7_at_blob(1700000000123)), uuid_version(uuid7_at_blob(0))",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(timestamp, 1_700_000_000_123);
+    assert_eq!(blob_timestamp, 1_700_000_000_123);
+    assert_eq!(version, 7);
+
+    let (negative, too_large, text): (Option<String>, Option<String>, Option<Vec<u8>>) = conn
+        .query_row(
+            "SELECT uuid7_at(-1), uuid7_at(0x1000000000000), uuid7_at_blob('1000')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(negative, None);
+    assert_eq!(too_large, None);
+    assert_eq!(text, None);
+}
+
+/// Tests that `uuid_generate_for_shard` returns UUIDs routed to the requested
+/// shard by `uuid_shard`.
+#[wasm_bindgen_test]
+fn test_uuid_generate_for_shard() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (shard, version): (i64, i64) = conn
+        .query_row(
+            "SELECT uuid_shard(u, 8), uuid_version(u) \
+             FROM (SELECT uuid_generate_for_shard(8, 3) AS u)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(shard, 3);
+    assert_eq!(version, 4);
+
+    let shard: i64 = conn
+        .query_row("SELECT uuid_shard('00000000-0000-4000-8000-00000000000b', 4)", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(shard, 3);
+
+    let (unreachable, no_shards, invalid): (Option<String>, Option<i64>, Option<i64>) = conn
+        .query_row(
+            "SELECT uuid_generate_for_shard(8, 8), uuid_shard(uuid(), 0), \
+             uuid_shard('not-a-uuid', 8)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(unreachable, None);
+    assert_eq!(no_shards, None);
+    assert_eq!(invalid, None);
+}
+
+/// Tests that the strict entry point raises errors on malformed UUIDs while
+/// the default one returns `NULL`.
+#[wasm_bindgen_test]
+fn test_strict_mode() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let lenient: Option<String> =
+        conn.query_row("SELECT uuid_str('not-a-uuid')", [], |r| r.get(0)).unwrap();
+    assert_eq!(lenient, None);
+
+    let strict = Connection::open_in_memory().unwrap();
+    let rc = unsafe {
+        sqlite_wasm_uuid_rs::sqlite3_uuid_init_strict(
+            strict.handle().cast(),
+            core::ptr::null_mut(),
+            core::ptr::null(),
+        )
+    };
+    assert_eq!(rc, rusqlite::ffi::SQLITE_OK);
+
+    let error = strict
+        .query_row("SELECT uuid_str('not-a-uuid')", [], |r| r.get::<_, Option<String>>(0))
+        .unwrap_err();
+    assert!(error.to_string().contains("invalid UUID argument"), "unexpected error: {error}");
+    assert!(strict.query_row("SELECT uuid_blob(X'0011')", [], |r| r.get::<_, Vec<u8>>(0)).is_err());
+
+    let (null, valid): (Option<String>, String) = strict
+        .query_row(
+            "SELECT uuid_str(NULL), uuid_str('00000000-0000-0000-0000-000000000000')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(null, None);
+    assert_eq!(valid, "00000000-0000-0000-0000-000000000000");
+}
+
+/// Tests that `uuid_encode_url_path` returns stable URL-safe slugs of the
+/// requested length.
+#[wasm_bindgen_test]
+fn test_uuid_encode_url_path() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let id = "017f22e2-79b0-7cc3-98c4-dc0c0c07398f";
+    for length in 1..=22 {
+        let slug: String = conn
+            .query_row("SELECT uuid_encode_url_path(?1, ?2)", (id, length), |r| r.get(0))
+            .unwrap();
+        assert_eq!(slug.len(), usize::try_from(length).unwrap());
+        assert!(slug.chars().all(|c| c.is_ascii_alphanumeric()), "{slug} is not URL-safe");
+    }
+
+    let (first, second, from_blob, longer): (String, String, String, String) = conn
+        .query_row(
+            "SELECT uuid_encode_url_path(?1, 8), uuid_encode_url_path(?1, 8), \
+             uuid_encode_url_path(uuid_blob(?1), 8), uuid_encode_url_path(?1, 12)",
+            [id],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first, from_blob);
+    assert!(longer.starts_with(&first));
+
+    let (nil, max): (String, String) = conn
+        .query_row(
+            "SELECT uuid_encode_url_path(uuid_nil(), 4), uuid_encode_url_path(uuid_max(), 4)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(nil, "0000");
+    assert_eq!(max, "zzzz");
+
+    let (empty, too_long, invalid): (Option<String>, Option<String>, Option<String>) = conn
+        .query_row(
+            "SELECT uuid_encode_url_path(uuid(), 0), uuid_encode_url_path(uuid(), 23), \
+             uuid_encode_url_path('not-a-uuid', 8)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(empty, None);
+    assert_eq!(too_long, None);
+    assert_eq!(invalid, None);
+}
+
+/// Tests that a successful `sqlite3_uuid_init` leaves its error message
+/// out-parameter untouched, as failures are the only case in which it is set.
+#[wasm_bindgen_test]
+fn test_init_error_message_untouched_on_success() {
+    let conn = Connection::open_in_memory().unwrap();
+    let mut error_message: *mut core::ffi::c_char = core::ptr::null_mut();
+    let rc = unsafe {
+        sqlite_wasm_uuid_rs::sqlite3_uuid_init(
+            conn.handle().cast(),
+            &raw mut error_message,
+            core::ptr::null(),
+        )
+    };
+    assert_eq!(rc, rusqlite::ffi::SQLITE_OK);
+    assert!(error_message.is_null());
+
+    let u: String = conn.query_row("SELECT uuid()", [], |r| r.get(0)).unwrap();
+    assert_eq!(u.len(), 36);
+}
+
+/// Tests that in WASM both entry points succeed with null API routines, the
+/// linked SQLite implementation being called directly.
+#[wasm_bindgen_test]
+fn test_init_with_null_api_routines() {
+    for init in
+        [sqlite_wasm_uuid_rs::sqlite3_uuid_init, sqlite_wasm_uuid_rs::sqlite3_uuid_init_strict]
+    {
+        let conn = Connection::open_in_memory().unwrap();
+        let rc = unsafe { init(conn.handle().cast(), core::ptr::null_mut(), core::ptr::null()) };
+        assert_eq!(rc, rusqlite::ffi::SQLITE_OK);
+
+        let text: String = conn
+            .query_row(
+                "SELECT uuid_str(uuid_blob('6ba7b810-9dad-11d1-80b4-00c04fd430c8'))",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(text, "6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+    }
+}
+
+/// Tests that `generate_uuid_blobs` returns the requested number of distinct
+/// UUIDv4s.
+#[wasm_bindgen_test]
+fn test_generate_uuid_blobs() {
+    let blobs = sqlite_wasm_uuid_rs::generate_uuid_blobs(1000);
+    assert_eq!(blobs.len(), 1000);
+
+    let distinct: BTreeSet<[u8; 16]> = blobs.iter().copied().collect();
+    assert_eq!(distinct.len(), blobs.len());
+    for blob in &blobs {
+        let u = Uuid::from_bytes(*blob);
+        assert_eq!(u.get_version_num(), 4);
+        assert_eq!(u.get_variant(), uuid::Variant::RFC4122);
+    }
+
+    assert!(sqlite_wasm_uuid_rs::generate_uuid_blobs(0).is_empty());
+}
+
+/// Tests that `register_on` and `register_on_with_set` register the functions
+/// on the given connection only.
+#[wasm_bindgen_test]
+fn test_register_on() {
+    use sqlite_wasm_uuid_rs::FunctionSet;
+
+    // Drop the auto-extension installed by the other tests, so that only the
+    // connection passed to `register_on` gets the functions.
+    unsafe {
+        sqlite_wasm_uuid_rs::unregister();
+    }
+    let registered = Connection::open_in_memory().unwrap();
+    let unregistered = Connection::open_in_memory().unwrap();
+    unsafe {
+        sqlite_wasm_uuid_rs::register_on(registered.handle().cast())
+            .expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+
+    let u: String = registered.query_row("SELECT uuid()", [], |r| r.get(0)).unwrap();
+    assert_eq!(u.len(), 36);
+    assert!(unregistered.query_row("SELECT uuid()", [], |r| r.get::<_, String>(0)).is_err());
+
+    // `register_on` ignores the selection of `register_function_set`, which
+    // `register_on_with_set` replaces with its own.
+    unsafe {
+        sqlite_wasm_uuid_rs::register_function_set(FunctionSet::V7)
+            .expect("Failed to register sqlite-wasm-uuid-rs");
+        sqlite_wasm_uuid_rs::unregister();
+    }
+    let full = Connection::open_in_memory().unwrap();
+    let subset = Connection::open_in_memory().unwrap();
+    unsafe {
+        sqlite_wasm_uuid_rs::register_on(full.handle().cast())
+            .expect("Failed to register sqlite-wasm-uuid-rs");
+        sqlite_wasm_uuid_rs::register_on_with_set(subset.handle().cast(), FunctionSet::V4)
+            .expect("Failed to register sqlite-wasm-uuid-rs");
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let u: String = full.query_row("SELECT uuid_str(uuid())", [], |r| r.get(0)).unwrap();
+    assert_eq!(u.len(), 36);
+    let u: String = subset.query_row("SELECT uuid()", [], |r| r.get(0)).unwrap();
+    assert_eq!(u.len(), 36);
+    assert!(subset.query_row("SELECT uuid7()", [], |r| r.get::<_, String>(0)).is_err());
+}
+
+/// Tests that `uuid_parse_relaxed_blob` recovers UUIDs from length-prefixed
+/// BLOBs and rejects unrecoverable ones.
+#[wasm_bindgen_test]
+fn test_uuid_parse_relaxed_blob() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let u = Uuid::parse_str("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap();
+    let parse = |blob: &[u8]| -> Option<Vec<u8>> {
+        conn.query_row("SELECT uuid_parse_relaxed_blob(?1)", [blob], |r| r.get(0)).unwrap()
+    };
+
+    assert_eq!(parse(u.as_bytes()).as_deref(), Some(u.as_bytes().as_slice()));
+
+    for prefix in [&[0x10][..], &[0x00, 0x10], &[0x10, 0x00], &[0, 0, 0, 0x10], &[0x10, 0, 0, 0]] {
+        let blob = [prefix, u.as_bytes().as_slice()].concat();
+        assert_eq!(parse(&blob).as_deref(), Some(u.as_bytes().as_slice()), "prefix {prefix:?}");
+    }
+
+    let nineteen = [&[0x00, 0x00, 0x10][..], u.as_bytes().as_slice()].concat();
+    assert_eq!(parse(&nineteen), None);
+    let wrong_length = [&[0, 0, 0, 0x11][..], u.as_bytes().as_slice()].concat();
+    assert_eq!(parse(&wrong_length), None);
+    assert_eq!(parse(&u.as_bytes()[..15]), None);
+
+    let text: Option<Vec<u8>> = conn
+        .query_row("SELECT uuid_parse_relaxed_blob(?1)", [u.to_string()], |r| r.get(0))
+        .unwrap();
+    assert_eq!(text, None);
+}
+
+/// Stress-tests that `uuid7()` and `uuid7_blob()` are strictly increasing
+/// when generated back-to-back, even within the same millisecond.
+#[wasm_bindgen_test]
+fn test_uuid7_monotonic() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let mut text_statement = conn.prepare("SELECT uuid7()").unwrap();
+    let mut blob_statement = conn.prepare("SELECT uuid7_blob()").unwrap();
+
+    let mut previous_text = String::new();
+    let mut previous_blob = Vec::new();
+    for _ in 0..10_000 {
+        let text: String = text_statement.query_row([], |r| r.get(0)).unwrap();
+        assert!(text > previous_text, "{text} is not greater than {previous_text}");
+        previous_text = text;
+
+        let blob: Vec<u8> = blob_statement.query_row([], |r| r.get(0)).unwrap();
+        assert!(blob > previous_blob, "{blob:?} is not greater than {previous_blob:?}");
+        previous_blob = blob;
+    }
+}
+
+/// Tests that `uuid_now_v7_text_batch` returns a strictly sorted JSON array
+/// of UUIDv7s.
+#[wasm_bindgen_test]
+fn test_uuid_now_v7_text_batch() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let batch: Vec<(String, i64)> = conn
+        .prepare(
+            "SELECT value, uuid_version(value) FROM json_each(uuid_now_v7_text_batch(500)) \
+             ORDER BY key",
+        )
+        .unwrap()
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(batch.len(), 500);
+    assert!(batch.iter().all(|(_, version)| *version == 7));
+    assert!(batch.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+    let (empty, too_many, negative): (String, Option<String>, Option<String>) = conn
+        .query_row(
+            "SELECT uuid_now_v7_text_batch(0), uuid_now_v7_text_batch(10001), \
+             uuid_now_v7_text_batch(-1)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(empty, "[]");
+    assert_eq!(too_many, None);
+    assert_eq!(negative, None);
+}
+
+/// Tests that the `UUID` collating sequence compares TEXT UUIDs by value and
+/// orders them like their BLOB form, but, as SQLite only applies collations
+/// to TEXT values, never matches a BLOB column without `uuid_str`.
+#[wasm_bindgen_test]
+fn test_uuid_collation() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    conn.execute_batch(
+        "CREATE TABLE text_ids (id TEXT);
+         CREATE TABLE blob_ids (id BLOB);
+         INSERT INTO text_ids VALUES (upper('017f22e2-79b0-7cc3-98c4-dc0c0c07398f'));
+         INSERT INTO blob_ids VALUES (uuid_blob('017f22e2-79b0-7cc3-98c4-dc0c0c07398f'));",
+    )
+    .unwrap();
+
+    let (direct, normalized): (i64, i64) = conn
+        .query_row(
+            "SELECT (SELECT count(*) FROM text_ids, blob_ids \
+                 WHERE text_ids.id = blob_ids.id COLLATE UUID), \
+             (SELECT count(*) FROM text_ids, blob_ids \
+                 WHERE text_ids.id = uuid_str(blob_ids.id) COLLATE UUID)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(direct, 0);
+    assert_eq!(normalized, 1);
+
+    let (binary, collated, simple): (i64, i64, i64) = conn
+        .query_row(
+            "SELECT 'ABCDEF00-0000-4000-8000-000000000000' = 'abcdef00-0000-4000-8000-000000000000', \
+             'ABCDEF00-0000-4000-8000-000000000000' = 'abcdef00-0000-4000-8000-000000000000' COLLATE UUID, \
+             'abcdef00000040008000000000000000' = 'abcdef00-0000-4000-8000-000000000000' COLLATE UUID",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(binary, 0);
+    assert_eq!(collated, 1);
+    assert_eq!(simple, 1);
+
+    conn.execute_batch(
+        "CREATE TABLE mixed (id TEXT);
+         INSERT INTO mixed VALUES ('B0000000-0000-4000-8000-000000000000'),
+             ('a0000000-0000-4000-8000-000000000000'), ('not-a-uuid'),
+             ('C0000000-0000-4000-8000-000000000000');",
+    )
+    .unwrap();
+    let by_text: Vec<String> = conn
+        .prepare("SELECT id FROM mixed ORDER BY id COLLATE UUID")
+        .unwrap()
+        .query_map([], |r| r.get(0))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    let by_blob: Vec<String> = conn
+        .prepare("SELECT id FROM mixed WHERE uuid_blob(id) IS NOT NULL ORDER BY uuid_blob(id)")
+        .unwrap()
+        .query_map([], |r| r.get(0))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(by_text[..3], by_blob[..]);
+    assert_eq!(by_text[3], "not-a-uuid");
+}
+
+/// Tests that `uuid_mask_node` zeroes the node of UUIDv1s while preserving
+/// their timestamp, and leaves other versions unchanged.
+#[wasm_bindgen_test]
+fn test_uuid_mask_node() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (node, timestamp, masked_timestamp, version, clock_seq): (i64, i64, i64, i64, i64) = conn
+        .query_row(
+            "SELECT uuid_node(m), uuid_extract_timestamp(u), uuid_extract_timestamp(m), \
+             uuid_version(m), uuid_clock_seq(m) \
+             FROM (SELECT u, uuid_mask_node(u) AS m \
+                   FROM (SELECT uuid_v1_with_clock_seq(0x123456789ABC, 42) AS u))",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?)),
+        )
+        .unwrap();
+    assert_eq!(node, 0);
+    assert_eq!(masked_timestamp, timestamp);
+    assert_eq!(version, 1);
+    assert_eq!(clock_seq, 42);
+
+    let masked_vector: String = conn
+        .query_row(
+            "SELECT uuid_mask_node(blob) FROM uuid_test_vectors WHERE version = 6",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert_eq!(masked_vector, "1ec9414c-232a-6b00-b3c8-000000000000");
+
+    let (v4, invalid): (String, Option<String>) = conn
+        .query_row(
+            "SELECT uuid_mask_node('919108f7-52d1-4320-9bac-f847db4148a8'), \
+             uuid_mask_node('not-a-uuid')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(v4, "919108f7-52d1-4320-9bac-f847db4148a8");
+    assert_eq!(invalid, None);
+}
+
+/// Tests that `uuid_extract_timestamp` returns milliseconds since the Unix
+/// epoch, including negative ones for UUIDv1s from before 1970.
+#[wasm_bindgen_test]
+fn test_uuid_extract_timestamp() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    // The RFC 9562 UUIDv1 and UUIDv7 examples share the same timestamp.
+    let (v1, v7, gregorian_epoch, last_tick, v4): (i64, i64, i64, i64, Option<i64>) = conn
+        .query_row(
+            "SELECT uuid_extract_timestamp('C232AB00-9414-11EC-B3C8-9F6BDECED846'), \
+             uuid_extract_timestamp('017F22E2-79B0-7CC3-98C4-DC0C0C07398F'), \
+             uuid_extract_timestamp('00000000-0000-1000-8000-000000000000'), \
+             uuid_extract_timestamp('13813fff-1dd2-11b2-8000-000000000000'), \
+             uuid_extract_timestamp('919108f7-52d1-4320-9bac-f847db4148a8')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?)),
+        )
+        .unwrap();
+    assert_eq!(v1, 1_645_557_742_000);
+    assert_eq!(v7, 1_645_557_742_000);
+    // 1582-10-15, the start of the UUIDv1 clock.
+    assert_eq!(gregorian_epoch, -12_219_292_800_000);
+    // 100ns before the Unix epoch.
+    assert_eq!(last_tick, -1);
+    assert_eq!(v4, None);
+}
+
+/// Tests that UUID arguments are accepted in the URN and braced forms, in any
+/// case, alongside the plain forms.
+#[wasm_bindgen_test]
+fn test_urn_and_braced_arguments() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let expected = Uuid::parse_str("12345678-1234-1234-1234-123456789abc").unwrap();
+
+    for input in [
+        "12345678-1234-1234-1234-123456789abc",
+        "12345678123412341234123456789abc",
+        "urn:uuid:12345678-1234-1234-1234-123456789abc",
+        "URN:UUID:12345678-1234-1234-1234-123456789ABC",
+        "{12345678-1234-1234-1234-123456789abc}",
+        "{12345678-1234-1234-1234-123456789ABC}",
+    ] {
+        let blob: Option<Vec<u8>> =
+            conn.query_row("SELECT uuid_blob(?1)", [input], |r| r.get(0)).unwrap();
+        assert_eq!(blob.as_deref(), Some(expected.as_bytes().as_slice()), "input {input}");
+    }
+
+    let (unclosed, bad_prefix): (Option<Vec<u8>>, Option<Vec<u8>>) = conn
+        .query_row(
+            "SELECT uuid_blob('{12345678-1234-1234-1234-123456789abc'), \
+             uuid_blob('urn:guid:12345678-1234-1234-1234-123456789abc')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(unclosed, None);
+    assert_eq!(bad_prefix, None);
+}
+
+/// Tests that `uuid_compare_version` compares timestamped UUIDs of different
+/// versions by their embedded timestamp.
+#[wasm_bindgen_test]
+fn test_uuid_compare_version() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let at = |seconds: u64, millis: u32| {
+        uuid::Timestamp::from_unix(uuid::NoContext, seconds, millis * 1_000_000)
+    };
+    let v1 = Uuid::new_v1(at(1_700_000_000, 123), &[1, 2, 3, 4, 5, 6]);
+    let v7 = Uuid::new_v7(at(1_700_000_000, 123));
+    let later_v7 = Uuid::new_v7(at(1_700_000_000, 124));
+    let compare = |a: &Uuid, b: &Uuid| -> Option<i64> {
+        conn.query_row(
+            "SELECT uuid_compare_version(?1, ?2)",
+            (a.to_string(), b.as_bytes().as_slice()),
+            |r| r.get(0),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(compare(&v1, &v7), Some(0));
+    assert_eq!(compare(&v7, &v1), Some(0));
+    assert_eq!(compare(&v1, &later_v7), Some(-1));
+    assert_eq!(compare(&later_v7, &v1), Some(1));
+
+    // A UUIDv1 from before the Unix epoch is older than any UUIDv7.
+    let gregorian_epoch = Uuid::parse_str("00000000-0000-1000-8000-000000000000").unwrap();
+    assert_eq!(compare(&gregorian_epoch, &v7), Some(-1));
+    assert_eq!(compare(&v7, &gregorian_epoch), Some(1));
+
+    let v4 = Uuid::parse_str("919108f7-52d1-4320-9bac-f847db4148a8").unwrap();
+    assert_eq!(compare(&v4, &v4), Some(0));
+    assert_eq!(compare(&v4, &v7), Some(if v4 < v7 { -1 } else { 1 }));
+
+    let invalid: Option<i64> = conn
+        .query_row("SELECT uuid_compare_version('not-a-uuid', uuid7())", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(invalid, None);
+}
+
+/// Tests the `uuid_urn` function.
+#[wasm_bindgen_test]
+fn test_uuid_urn() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let (text, blob, round_trip, invalid): (String, String, String, Option<String>) = conn
+        .query_row(
+            "SELECT uuid_urn('12345678-1234-1234-1234-123456789ABC'), \
+             uuid_urn(uuid_blob('12345678-1234-1234-1234-123456789abc')), \
+             uuid_str(uuid_urn('12345678-1234-1234-1234-123456789abc')), uuid_urn('not-a-uuid')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(text, "urn:uuid:12345678-1234-1234-1234-123456789abc");
+    assert_eq!(blob, text);
+    assert_eq!(round_trip, "12345678-1234-1234-1234-123456789abc");
+    assert_eq!(invalid, None);
+
+    // The strict entry point raises an error instead.
+    let strict = Connection::open_in_memory().unwrap();
+    let rc = unsafe {
+        sqlite_wasm_uuid_rs::sqlite3_uuid_init_strict(
+            strict.handle().cast(),
+            core::ptr::null_mut(),
+            core::ptr::null(),
+        )
+    };
+    assert_eq!(rc, rusqlite::ffi::SQLITE_OK);
+    assert!(strict
+        .query_row("SELECT uuid_urn('not-a-uuid')", [], |r| r.get::<_, Option<String>>(0))
+        .is_err());
+    assert_eq!(
+        strict.query_row("SELECT uuid_urn(NULL)", [], |r| r.get::<_, Option<String>>(0)).unwrap(),
+        None
+    );
+}
+
+/// Tests the `format` argument of `uuid_str`.
+#[wasm_bindgen_test]
+fn test_uuid_str_format() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let id = "12345678-1234-1234-ABCD-123456789abc";
+
+    for (format, expected) in [
+        ("hyphenated", "12345678-1234-1234-abcd-123456789abc"),
+        ("simple", "1234567812341234abcd123456789abc"),
+        ("braced", "{12345678-1234-1234-abcd-123456789abc}"),
+        ("upper", "12345678-1234-1234-ABCD-123456789ABC"),
+        ("simple_upper", "1234567812341234ABCD123456789ABC"),
+    ] {
+        let formatted: String =
+            conn.query_row("SELECT uuid_str(?1, ?2)", [id, format], |r| r.get(0)).unwrap();
+        assert_eq!(formatted, expected, "format {format}");
+    }
+
+    let (default, unknown, null_format, invalid): (
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    ) = conn
+        .query_row(
+            "SELECT uuid_str(?1), uuid_str(?1, 'lower'), uuid_str(?1, NULL), \
+             uuid_str('not-a-uuid', 'simple')",
+            [id],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(default, "12345678-1234-1234-abcd-123456789abc");
+    assert_eq!(unknown, None);
+    assert_eq!(null_format, None);
+    assert_eq!(invalid, None);
+}
+
+/// Tests the `uuid_is_valid` function.
+#[wasm_bindgen_test]
+fn test_uuid_is_valid() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let results: (i64, i64, i64, i64, i64) = conn
+        .query_row(
+            "SELECT uuid_is_valid('not-a-uuid'), uuid_is_valid(uuid()), uuid_is_valid(X'00'), \
+             uuid_is_valid(uuid_blob()), uuid_is_valid(NULL)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?)),
+        )
+        .unwrap();
+    assert_eq!(results, (0, 1, 0, 1, 0));
+
+    conn.execute_batch(
+        "CREATE TABLE legacy (id TEXT);
+         INSERT INTO legacy VALUES (uuid()), ('oops'), (uuid7()), ('');",
+    )
+    .unwrap();
+    let invalid: Vec<String> = conn
+        .prepare("SELECT id FROM legacy WHERE NOT uuid_is_valid(id) ORDER BY id")
+        .unwrap()
+        .query_map([], |r| r.get(0))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(invalid, ["", "oops"]);
+}
+
+/// Tests that `uuid_compare` orders UUIDs by their bytes, whether given as
+/// TEXT or BLOB.
+#[wasm_bindgen_test]
+fn test_uuid_compare() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let compare = |sql: &str| -> Option<i64> { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+
+    let u = "919108f7-52d1-4320-9bac-f847db4148a8";
+    let same: Option<i64> =
+        conn.query_row("SELECT uuid_compare(uuid_blob(?1), ?1)", [u], |r| r.get(0)).unwrap();
+    assert_eq!(same, Some(0));
+
+    assert_eq!(compare("SELECT uuid_compare(uuid_nil(), uuid_max())"), Some(-1));
+    assert_eq!(compare("SELECT uuid_compare(uuid_max_blob(), uuid_nil())"), Some(1));
+    assert_eq!(compare("SELECT uuid_compare('not-a-uuid', uuid_nil())"), None);
+    assert_eq!(compare("SELECT uuid_compare(uuid_nil(), NULL)"), None);
+
+    // The strict entry point raises an error instead.
+    let strict = Connection::open_in_memory().unwrap();
+    let rc = unsafe {
+        sqlite_wasm_uuid_rs::sqlite3_uuid_init_strict(
+            strict.handle().cast(),
+            core::ptr::null_mut(),
+            core::ptr::null(),
+        )
+    };
+    assert_eq!(rc, rusqlite::ffi::SQLITE_OK);
+    assert!(strict
+        .query_row("SELECT uuid_compare('not-a-uuid', uuid_nil())", [], |r| r
+            .get::<_, Option<i64>>(0))
+        .is_err());
+    assert!(strict
+        .query_row("SELECT uuid_compare(NULL, 'not-a-uuid')", [], |r| r.get::<_, Option<i64>>(0))
+        .is_err());
+    assert_eq!(
+        strict
+            .query_row("SELECT uuid_compare(uuid_nil(), NULL)", [], |r| r.get::<_, Option<i64>>(0))
+            .unwrap(),
+        None
+    );
+}
+
+/// Tests that connections registered with the same seed generate the same
+/// UUIDv4 sequence, from `uuid()` as well as `uuid_series`, and that
+/// `register` restores random generation.
+#[wasm_bindgen_test]
+fn test_register_with_seed() {
+    let first_uuids = |conn: &Connection| -> Vec<String> {
+        (0..3).map(|_| conn.query_row("SELECT uuid()", [], |r| r.get(0)).unwrap()).collect()
+    };
+
+    unsafe {
+        sqlite_wasm_uuid_rs::register_with_seed(42)
+            .expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let a = Connection::open_in_memory().unwrap();
+    let b = Connection::open_in_memory().unwrap();
+    let uuids = first_uuids(&a);
+    assert_eq!(uuids, first_uuids(&b));
+    for u in &uuids {
+        assert_eq!(Uuid::parse_str(u).unwrap().get_version_num(), 4);
+    }
+
+    let blob: Vec<u8> = b.query_row("SELECT uuid_blob()", [], |r| r.get(0)).unwrap();
+    assert_eq!(Uuid::from_slice(&blob).unwrap().get_version_num(), 4);
+    assert_ne!(Uuid::from_slice(&blob).unwrap().to_string(), uuids[0]);
+
+    unsafe {
+        sqlite_wasm_uuid_rs::register_with_seed(7).expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let c = Connection::open_in_memory().unwrap();
+    assert_ne!(first_uuids(&c), uuids);
+
+    let series = Connection::open_in_memory().unwrap();
+    let series_uuids: Vec<String> = series
+        .prepare("SELECT uuid FROM uuid_series(3)")
+        .unwrap()
+        .query_map([], |r| r.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(series_uuids, first_uuids(&Connection::open_in_memory().unwrap()));
+    assert_ne!(first_uuids(&series), series_uuids);
+
+    let rng: String =
+        series.query_row("SELECT json_extract(uuid_probe(), '$.rng')", [], |r| r.get(0)).unwrap();
+    assert_eq!(rng, "ChaCha8Rng");
+
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let d = Connection::open_in_memory().unwrap();
+    let e = Connection::open_in_memory().unwrap();
+    assert_ne!(first_uuids(&d), first_uuids(&e));
+    let rng: String =
+        d.query_row("SELECT json_extract(uuid_probe(), '$.rng')", [], |r| r.get(0)).unwrap();
+    assert_eq!(rng, "crypto.getRandomValues");
+}
+
+/// Tests that `uuid7_datetime` formats the timestamp of a UUIDv7 in UTC.
+#[wasm_bindgen_test]
+fn test_uuid7_datetime() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let datetime = |sql: &str| -> Option<String> { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+
+    let epoch = datetime("SELECT uuid7_datetime(uuid7_at(0))").unwrap();
+    assert!(epoch.starts_with("1970-01-01"));
+    assert_eq!(epoch, "1970-01-01 00:00:00.000");
+
+    assert_eq!(
+        datetime("SELECT uuid7_datetime(uuid7_at_blob(1645557742123))").as_deref(),
+        Some("2022-02-22 19:22:22.123")
+    );
+    assert_eq!(
+        datetime("SELECT uuid7_datetime('017f22e2-79b0-7cc3-98c4-dc0c0c07398f')").as_deref(),
+        Some("2022-02-22 19:22:22.000")
+    );
+    assert_eq!(
+        datetime("SELECT uuid7_datetime(uuid7_at(951782400000))").as_deref(),
+        Some("2000-02-29 00:00:00.000")
+    );
+
+    let roundtrip: i64 = conn
+        .query_row(
+            "SELECT CAST((julianday(uuid7_datetime(uuid7_at(1700000000456))) - 2440587.5) \
+             * 86400000 + 0.5 AS INTEGER)",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert_eq!(roundtrip, 1700000000456);
+
+    assert_eq!(datetime("SELECT uuid7_datetime(uuid())"), None);
+    assert_eq!(datetime("SELECT uuid7_datetime('not-a-uuid')"), None);
+}
+
+/// Tests that text results are still produced for valid inputs, that
+/// arguments with an embedded NUL byte yield `NULL` instead of panicking, and
+/// that a text result with an embedded NUL byte raises an error.
+#[wasm_bindgen_test]
+fn test_embedded_nul_handling() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let text = |sql: &str| -> Option<String> { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+
+    let u = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+    assert_eq!(text(&format!("SELECT uuid_str('{u}')")).as_deref(), Some(u));
+    assert_eq!(
+        text(&format!("SELECT uuid_str('{u}', 'upper')")).as_deref(),
+        Some("6BA7B810-9DAD-11D1-80B4-00C04FD430C8")
+    );
+    assert_eq!(
+        text(&format!("SELECT uuid_urn('{u}')")).as_deref(),
+        Some("urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8")
+    );
+    assert_eq!(text("SELECT uuid()").map(|u| u.len()), Some(36));
+    assert_eq!(text("SELECT uuid7()").map(|u| u.len()), Some(36));
+
+    assert_eq!(text(&format!("SELECT uuid_str('{u}', 'upper' || char(0))")), None);
+    assert_eq!(text(&format!("SELECT uuid_urn(char(0) || '{u}')")), None);
+
+    // The hidden `text` column of `uuid_parse_many` echoes its argument, which
+    // cannot be returned with its NUL byte.
+    let error = conn
+        .query_row("SELECT text FROM uuid_parse_many('a' || char(0) || 'b')", [], |r| {
+            r.get::<_, String>(0)
+        })
+        .unwrap_err();
+    assert!(error.to_string().contains("result contains a NUL byte"), "{error}");
+}
+
+/// Tests that `register_function_set` only registers the selected functions,
+/// and that `register` restores all of them.
+#[wasm_bindgen_test]
+fn test_register_function_set() {
+    use sqlite_wasm_uuid_rs::FunctionSet;
+
+    unsafe {
+        sqlite_wasm_uuid_rs::register_function_set(FunctionSet::V7)
+            .expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let u: String = conn.query_row("SELECT uuid7()", [], |r| r.get(0)).unwrap();
+    assert_eq!(u.len(), 36);
+    let blob: Vec<u8> = conn.query_row("SELECT uuid7_blob()", [], |r| r.get(0)).unwrap();
+    assert_eq!(blob.len(), 16);
+    let err = conn.query_row("SELECT uuid()", [], |r| r.get::<_, String>(0)).unwrap_err();
+    assert!(err.to_string().contains("no such function"), "unexpected error {err}");
+    assert!(conn.query_row("SELECT uuid_str(uuid7())", [], |r| r.get::<_, String>(0)).is_err());
+    assert!(conn
+        .query_row("SELECT count(*) FROM uuid_test_vectors", [], |r| r.get::<_, i64>(0))
+        .is_err());
+
+    unsafe {
+        sqlite_wasm_uuid_rs::register_function_set(FunctionSet::V4 | FunctionSet::CONVERSION)
+            .expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let u: String = conn.query_row("SELECT uuid_str(uuid_blob())", [], |r| r.get(0)).unwrap();
+    assert_eq!(u.len(), 36);
+    assert!(conn.query_row("SELECT uuid7()", [], |r| r.get::<_, String>(0)).is_err());
+
+    // The manifest only lists the registered functions.
+    unsafe {
+        sqlite_wasm_uuid_rs::register_function_set(FunctionSet::V7 | FunctionSet::UTILITIES)
+            .expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let subset = Connection::open_in_memory().unwrap();
+    let manifest =
+        || -> String { subset.query_row("SELECT uuid_sql_manifest()", [], |r| r.get(0)).unwrap() };
+    let subset_manifest = manifest();
+    assert!(subset_manifest.contains("uuid7(0) non-deterministic"));
+    assert!(subset_manifest.contains("uuid7_max(1) deterministic aggregate"));
+    assert!(subset_manifest.contains("uuid_sql_manifest(0) deterministic"));
+    for absent in ["uuid_str(", "uuid(", "table-valued", "collation"] {
+        assert!(!subset_manifest.contains(absent), "unexpected manifest {subset_manifest}");
+    }
+
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let u: String = conn.query_row("SELECT uuid_str(uuid7())", [], |r| r.get(0)).unwrap();
+    assert_eq!(u.len(), 36);
+    // Connections opened before keep listing the functions they registered.
+    assert_eq!(manifest(), subset_manifest);
+}
+
+/// Tests that `uuid_blob` canonicalizes the case and hyphenation variants of
+/// a UUID to the same bytes, so that BLOB keys deduplicate them.
+#[wasm_bindgen_test]
+fn test_uuid_blob_canonicalizes_case_and_hyphens() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let inputs = [
+        "12345678-1234-1234-1234-123456789abc",
+        "12345678-1234-1234-1234-123456789ABC",
+        "12345678-1234-1234-1234-123456789aBc",
+        "12345678123412341234123456789abc",
+        "12345678123412341234123456789ABC",
+    ];
+    let lowercase: Vec<u8> =
+        conn.query_row("SELECT uuid_blob(?1)", [inputs[0]], |r| r.get(0)).unwrap();
+    for input in inputs {
+        let blob: Vec<u8> = conn.query_row("SELECT uuid_blob(?1)", [input], |r| r.get(0)).unwrap();
+        assert_eq!(blob, lowercase, "input {input}");
+    }
+
+    // The same holds for UUIDs of other versions, e.g. a UUIDv7.
+    let v7: Vec<u8> = conn
+        .query_row("SELECT uuid_blob('017F22E279B07CC398C4DC0C0C07398F')", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(
+        v7,
+        Uuid::parse_str("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap().as_bytes().as_slice()
+    );
+
+    conn.execute_batch("CREATE TABLE items (id BLOB PRIMARY KEY)").unwrap();
+    for input in inputs {
+        conn.execute("INSERT OR IGNORE INTO items (id) VALUES (uuid_blob(?1))", [input]).unwrap();
+    }
+    let count: i64 = conn.query_row("SELECT count(*) FROM items", [], |r| r.get(0)).unwrap();
+    assert_eq!(count, 1);
+
+    for partial in [
+        "12345678123412341234123456789ab",
+        "1234567-81234-1234-1234-123456789abc",
+        "12345678-12341234-1234-123456789abc",
+    ] {
+        let blob: Option<Vec<u8>> =
+            conn.query_row("SELECT uuid_blob(?1)", [partial], |r| r.get(0)).unwrap();
+        assert_eq!(blob, None, "input {partial}");
+    }
+}
+
+/// Tests the safe Rust generation API, without a SQLite connection.
+#[wasm_bindgen_test]
+fn test_generate_without_connection() {
+    use sqlite_wasm_uuid_rs::generate;
+
+    let (a, b) = (generate::new_v4_string(), generate::new_v4_string());
+    assert_ne!(a, b);
+    assert_eq!(Uuid::parse_str(&a).unwrap().get_version_num(), 4);
+    assert_eq!(Uuid::from_bytes(generate::new_v4_blob()).get_version_num(), 4);
+
+    let v7 = Uuid::from_bytes(generate::now_v7_blob());
+    assert_eq!(v7.get_version_num(), 7);
+    let later = Uuid::parse_str(&generate::now_v7_string()).unwrap();
+    assert_eq!(later.get_version_num(), 7);
+    assert!(later > v7);
+}
+
+/// Tests that the Rust and SQL UUIDv7 share the same monotonic source.
+#[wasm_bindgen_test]
+fn test_generate_shares_v7_source_with_sql() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    let from_rust = sqlite_wasm_uuid_rs::generate::now_v7_string();
+    let from_sql: String = conn.query_row("SELECT uuid7()", [], |r| r.get(0)).unwrap();
+    let again = sqlite_wasm_uuid_rs::generate::now_v7_blob();
+    assert!(from_rust < from_sql);
+    assert!(Uuid::parse_str(&from_sql).unwrap() < Uuid::from_bytes(again));
+}
+
+/// Tests that registering twice is a no-op, and that `unregister` removes the
+/// functions from the connections opened afterwards.
+#[wasm_bindgen_test]
+fn test_register_idempotent_and_unregister() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs twice");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let u: String = conn.query_row("SELECT uuid()", [], |r| r.get(0)).unwrap();
+    assert_eq!(u.len(), 36);
+    let u7: String = conn.query_row("SELECT uuid7()", [], |r| r.get(0)).unwrap();
+    assert_eq!(u7.len(), 36);
+
+    unsafe {
+        sqlite_wasm_uuid_rs::unregister();
+        sqlite_wasm_uuid_rs::unregister();
+    }
+    let unregistered = Connection::open_in_memory().unwrap();
+    assert!(unregistered.query_row("SELECT uuid()", [], |r| r.get::<_, String>(0)).is_err());
+    // Connections opened before keep their functions.
+    assert!(conn.query_row("SELECT uuid()", [], |r| r.get::<_, String>(0)).is_ok());
+
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs again");
+    }
+    let registered = Connection::open_in_memory().unwrap();
+    assert!(registered.query_row("SELECT uuid()", [], |r| r.get::<_, String>(0)).is_ok());
+}
+
+/// Tests that `uuid_base32` and `uuid_from_base32` round-trip UUIDs through
+/// Crockford Base32.
+#[wasm_bindgen_test]
+fn test_uuid_base32() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let text = |sql: &str| -> Option<String> { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+
+    for input in [
+        "uuid_nil()",
+        "uuid_max()",
+        "uuid()",
+        "uuid7()",
+        "uuid_blob('017f22e2-79b0-7cc3-98c4-dc0c0c07398f')",
+    ] {
+        let (roundtrip, canonical): (String, String) = conn
+            .query_row(
+                &format!(
+                    "WITH t(u) AS (SELECT {input}) \
+                     SELECT uuid_from_base32(uuid_base32(u)), uuid_str(u) FROM t"
+                ),
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(roundtrip, canonical, "input {input}");
+    }
+
+    assert_eq!(
+        text("SELECT uuid_base32(uuid_nil())").as_deref(),
+        Some("00000000000000000000000000")
+    );
+    assert_eq!(
+        text("SELECT uuid_base32(uuid_max())").as_deref(),
+        Some("7ZZZZZZZZZZZZZZZZZZZZZZZZZ")
+    );
+    // ULID example from the specification, read as a UUID.
+    assert_eq!(
+        text("SELECT uuid_base32('01563e3a-b5d3-d676-4c61-efb99302bd5b')").as_deref(),
+        Some("01ARZ3NDEKTSV4RRFFQ69G5FAV")
+    );
+    assert_eq!(
+        text("SELECT uuid_from_base32('01arz3ndektsv4rrffq69g5fav')").as_deref(),
+        Some("01563e3a-b5d3-d676-4c61-efb99302bd5b")
+    );
+
+    // Base32 preserves the order of UUIDv7.
+    let ordered: bool = conn
+        .query_row("SELECT uuid_base32(uuid7_at(1000)) < uuid_base32(uuid7_at(2000))", [], |r| {
+            r.get(0)
+        })
+        .unwrap();
+    assert!(ordered);
+
+    for malformed in [
+        "'01ARZ3NDEKTSV4RRFFQ69G5FA'",
+        "'01ARZ3NDEKTSV4RRFFQ69G5FAVX'",
+        "'01ARZ3NDEKTSV4RRFFQ69G5FAU'",
+        "'81ARZ3NDEKTSV4RRFFQ69G5FAV'",
+        "NULL",
+    ] {
+        assert_eq!(text(&format!("SELECT uuid_from_base32({malformed})")), None, "{malformed}");
+    }
+    assert_eq!(text("SELECT uuid_base32('not-a-uuid')"), None);
+
+    // The strict entry point raises an error instead.
+    let strict = Connection::open_in_memory().unwrap();
+    let rc = unsafe {
+        sqlite_wasm_uuid_rs::sqlite3_uuid_init_strict(
+            strict.handle().cast(),
+            core::ptr::null_mut(),
+            core::ptr::null(),
+        )
+    };
+    assert_eq!(rc, rusqlite::ffi::SQLITE_OK);
+    assert!(strict
+        .query_row("SELECT uuid_base32('not-a-uuid')", [], |r| r.get::<_, Option<String>>(0))
+        .is_err());
+    assert_eq!(
+        strict
+            .query_row("SELECT uuid_base32(NULL)", [], |r| r.get::<_, Option<String>>(0))
+            .unwrap(),
+        None
+    );
+}
+
+/// Tests that `uuid_base64` and `uuid_from_base64` round-trip UUIDs through
+/// 22-character URL-safe Base64 tokens.
+#[wasm_bindgen_test]
+fn test_uuid_base64() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let text = |sql: &str| -> Option<String> { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+
+    for input in [
+        "uuid_nil()",
+        "uuid_max()",
+        "uuid()",
+        "uuid7()",
+        "uuid_blob('017f22e2-79b0-7cc3-98c4-dc0c0c07398f')",
+    ] {
+        let (encoded, roundtrip, canonical): (String, String, String) = conn
+            .query_row(
+                &format!(
+                    "WITH t(u) AS (SELECT {input}) \
+                     SELECT uuid_base64(u), uuid_from_base64(uuid_base64(u)), uuid_str(u) FROM t"
+                ),
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(encoded.len(), 22, "input {input}");
+        assert!(!encoded.contains(['+', '/', '=']), "input {input}");
+        assert_eq!(roundtrip, canonical, "input {input}");
+    }
+
+    assert_eq!(text("SELECT uuid_base64(uuid_nil())").as_deref(), Some("AAAAAAAAAAAAAAAAAAAAAA"));
+    assert_eq!(text("SELECT uuid_base64(uuid_max())").as_deref(), Some("_____________________w"));
+    assert_eq!(
+        text("SELECT uuid_base64('fbef9b7e-fbef-9b7e-fbef-9b7efbef9b7e')").as_deref(),
+        Some("---bfvvvm37775t----bfg")
+    );
+
+    assert_eq!(
+        text("SELECT uuid_from_base64('AX8i4nmwfMOYxNwMDAc5jw')").as_deref(),
+        Some("017f22e2-79b0-7cc3-98c4-dc0c0c07398f")
+    );
+
+    for malformed in [
+        "'AAAAAAAAAAAAAAAAAAAAA'",
+        "'AAAAAAAAAAAAAAAAAAAAAAA'",
+        "'AAAAAAAAAAAAAAAAAAAAA='",
+        "'AAAAAAAAAAAAAAAAAAAA+A'",
+        "'AAAAAAAAAAAAAAAAAAAAAB'",
+        "NULL",
+    ] {
+        assert_eq!(text(&format!("SELECT uuid_from_base64({malformed})")), None, "{malformed}");
+    }
+    assert_eq!(text("SELECT uuid_base64('not-a-uuid')"), None);
+}
+
+/// Tests that TEXT arguments are read according to their byte length, both
+/// when built by concatenation and when containing an embedded NUL byte.
+#[wasm_bindgen_test]
+fn test_text_argument_byte_length() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let text = |sql: &str| -> Option<String> { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+
+    assert_eq!(
+        text("SELECT uuid_str('6ba7b810-9dad' || '-11d1-80b4-' || '00c04fd430c8')").as_deref(),
+        Some("6ba7b810-9dad-11d1-80b4-00c04fd430c8")
+    );
+    assert_eq!(
+        text("SELECT uuid_str(substr('xx6ba7b810-9dad-11d1-80b4-00c04fd430c8yy', 3, 36))")
+            .as_deref(),
+        Some("6ba7b810-9dad-11d1-80b4-00c04fd430c8")
+    );
+    assert_eq!(
+        text("SELECT uuid_str(printf('%s-%s', '6ba7b810-9dad-11d1', '80b4-00c04fd430c8'))")
+            .as_deref(),
+        Some("6ba7b810-9dad-11d1-80b4-00c04fd430c8")
+    );
+    assert_eq!(text("SELECT uuid_str('6ba7b810-9dad-11d1-80b4-00c04fd430c8' || char(0))"), None);
+    assert_eq!(
+        text("SELECT uuid_str('6ba7b810-9dad-11d1-80b4-00c04fd430c8' || char(0) || 'junk')"),
+        None
+    );
+}
+
+/// Tests that `uuid7_strip` maps UUIDs to unrelated but deterministic UUIDv4.
+#[wasm_bindgen_test]
+fn test_uuid7_strip() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let strip = |u: &str| -> Option<String> {
+        conn.query_row("SELECT uuid7_strip(?1)", [u], |r| r.get(0)).unwrap()
+    };
+
+    let v7 = "017f22e2-79b0-7cc3-98c4-dc0c0c07398f";
+    let stripped = strip(v7).unwrap();
+    let parsed = Uuid::parse_str(&stripped).unwrap();
+    assert_eq!(parsed.get_version_num(), 4);
+    assert_eq!(parsed.get_variant(), uuid::Variant::RFC4122);
+    assert_ne!(stripped, v7);
+    assert_eq!(parsed.get_timestamp(), None);
+
+    // Deterministic, whatever the representation of the input.
+    assert_eq!(strip(v7), Some(stripped.clone()));
+    let from_blob: String =
+        conn.query_row("SELECT uuid7_strip(uuid_blob(?1))", [v7], |r| r.get(0)).unwrap();
+    assert_eq!(from_blob, stripped);
+
+    // UUIDv7 sharing their timestamp do not share a prefix once stripped.
+    let (a, b): (String, String) = conn
+        .query_row("SELECT uuid7_strip(uuid7_at(1000)), uuid7_strip(uuid7_at(1000))", [], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .unwrap();
+    assert_ne!(a[..8], b[..8]);
+
+    assert_eq!(strip("not-a-uuid"), None);
+}
+
+/// Tests that `uuid_variant` labels the variant field of UUIDs.
+#[wasm_bindgen_test]
+fn test_uuid_variant() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let variant = |sql: &str| -> Option<String> { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+
+    assert_eq!(variant("SELECT uuid_variant(uuid())").as_deref(), Some("rfc4122"));
+    assert_eq!(variant("SELECT uuid_variant(uuid7_blob())").as_deref(), Some("rfc4122"));
+    assert_eq!(
+        variant("SELECT uuid_variant('00000000-0000-0000-0000-000000000000')").as_deref(),
+        Some("ncs")
+    );
+    assert_eq!(
+        variant("SELECT uuid_variant('00000000-0000-0000-c000-000000000000')").as_deref(),
+        Some("microsoft")
+    );
+    assert_eq!(
+        variant("SELECT uuid_variant('ffffffff-ffff-ffff-ffff-ffffffffffff')").as_deref(),
+        Some("future")
+    );
+    assert_eq!(variant("SELECT uuid_variant('not-a-uuid')"), None);
+    assert_eq!(variant("SELECT uuid_variant(NULL)"), None);
+
+    // The strict entry point raises an error instead.
+    let strict = Connection::open_in_memory().unwrap();
+    let rc = unsafe {
+        sqlite_wasm_uuid_rs::sqlite3_uuid_init_strict(
+            strict.handle().cast(),
+            core::ptr::null_mut(),
+            core::ptr::null(),
+        )
+    };
+    assert_eq!(rc, rusqlite::ffi::SQLITE_OK);
+    assert!(strict
+        .query_row("SELECT uuid_variant('not-a-uuid')", [], |r| r.get::<_, Option<String>>(0))
+        .is_err());
+    assert_eq!(
+        strict
+            .query_row("SELECT uuid_variant(NULL)", [], |r| r.get::<_, Option<String>>(0))
+            .unwrap(),
+        None
+    );
+}
+
+/// Tests that UUID results remain intact once the function that produced them
+/// has returned, across statement resets and re-executions.
+#[wasm_bindgen_test]
+fn test_results_outlive_generating_uuid() {
+    use rusqlite::types::Value;
+
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE t(a BLOB, b BLOB, c BLOB, d TEXT);
+         WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 8)
+         INSERT INTO t SELECT uuid_blob(), uuid7_blob(),
+             uuid_blob(uuid_from_base64(uuid_base64(uuid()))), uuid7_strip(uuid7()) FROM n;",
+    )
+    .unwrap();
+
+    let mut stmt = conn
+        .prepare("SELECT a, b, c, d, uuid_blob(d), uuid_str(a), uuid_blob(b) FROM t ORDER BY rowid")
+        .unwrap();
+    let mut read = || -> Vec<Vec<Value>> {
+        stmt.query_map([], |r| (0..7).map(|i| r.get(i)).collect())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    };
+    let first = read();
+    // Executing the statement again resets it, releasing the previous results.
+    let second = read();
+    assert_eq!(first, second);
+    assert_eq!(first.len(), 8);
+
+    let blob = |value: &Value| match value {
+        Value::Blob(bytes) => Uuid::from_slice(bytes).unwrap(),
+        other => panic!("expected a BLOB, got {other:?}"),
+    };
+    let text = |value: &Value| match value {
+        Value::Text(text) => Uuid::parse_str(text).unwrap(),
+        other => panic!("expected a TEXT, got {other:?}"),
+    };
+    for row in &first {
+        assert_eq!(blob(&row[0]).get_version_num(), 4);
+        assert_eq!(blob(&row[1]).get_version_num(), 7);
+        assert_eq!(blob(&row[2]).get_version_num(), 4);
+        assert_eq!(text(&row[3]).get_version_num(), 4);
+        assert_eq!(blob(&row[4]), text(&row[3]));
+        assert_eq!(text(&row[5]), blob(&row[0]));
+        assert_eq!(blob(&row[6]), blob(&row[1]));
+    }
+}
+
+/// Tests the `uuid7_min` and `uuid7_max` aggregates.
+#[wasm_bindgen_test]
+fn test_uuid7_min_max() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE t(g INTEGER, id);
+         INSERT INTO t VALUES (1, uuid7_at(2000)), (1, upper(uuid7_at(3000))),
+             (1, uuid7_at(1000)), (1, NULL), (1, 'not-a-uuid'),
+             (2, uuid_blob(uuid7_at(5000))), (2, uuid_blob(uuid7_at(4000)));",
+    )
+    .unwrap();
+    let timestamps = |sql: &str| -> Vec<(i64, i64)> {
+        let mut stmt = conn.prepare(sql).unwrap();
+        stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    };
+
+    // Out-of-order insertions, the uppercase latest one included.
+    assert_eq!(
+        timestamps(
+            "SELECT uuid7_timestamp(uuid7_min(id)), uuid7_timestamp(uuid7_max(id)) \
+             FROM t GROUP BY g ORDER BY g"
+        ),
+        [(1000, 3000), (4000, 5000)]
+    );
+
+    // The result keeps the form of the input.
+    let (text, blob): (String, Vec<u8>) = conn
+        .query_row(
+            "SELECT (SELECT uuid7_max(id) FROM t WHERE g = 1), \
+             (SELECT uuid7_max(id) FROM t WHERE g = 2)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(text, text.to_lowercase());
+    assert_eq!(Uuid::parse_str(&text).unwrap().get_version_num(), 7);
+    assert_eq!(blob.len(), 16);
+
+    let empty: Option<String> =
+        conn.query_row("SELECT uuid7_max(id) FROM t WHERE g = 3", [], |r| r.get(0)).unwrap();
+    assert_eq!(empty, None);
+
+    let manifest: String = conn.query_row("SELECT uuid_sql_manifest()", [], |r| r.get(0)).unwrap();
+    assert!(manifest.contains("uuid7_max(1) deterministic aggregate"));
+}
+
+/// Tests that `RegisterError` keeps the raw SQLite code and describes it.
+#[wasm_bindgen_test]
+fn test_register_error() {
+    use rusqlite::ffi::{SQLITE_BUSY_RECOVERY, SQLITE_MISUSE, SQLITE_NOMEM};
+    use sqlite_wasm_uuid_rs::RegisterError;
+
+    let error = RegisterError::new(SQLITE_NOMEM);
+    assert_eq!(error.code(), SQLITE_NOMEM);
+    assert_eq!(core::ffi::c_int::from(error), SQLITE_NOMEM);
+    assert_eq!(
+        error.to_string(),
+        "failed to register the extension: out of memory (SQLite result code 7)"
+    );
+    assert_eq!(
+        RegisterError::new(SQLITE_MISUSE).to_string(),
+        "failed to register the extension: bad parameter or other API misuse (SQLite result code 21)"
+    );
+    // Extended codes are described by their primary code.
+    assert_eq!(
+        RegisterError::new(SQLITE_BUSY_RECOVERY).to_string(),
+        "failed to register the extension: database is busy (SQLite result code 261)"
+    );
+    assert_eq!(
+        RegisterError::new(1000).to_string(),
+        "failed to register the extension: unknown error (SQLite result code 1000)"
+    );
+
+    // Propagates through `?` into boxed errors.
+    let register = || -> Result<(), alloc::boxed::Box<dyn core::error::Error>> {
+        unsafe { sqlite_wasm_uuid_rs::register()? };
+        Ok(())
+    };
+    assert!(register().is_ok());
+}
+
+/// Tests the conversions between UUIDs and the Microsoft GUID byte order.
+#[wasm_bindgen_test]
+fn test_uuid_guid_byte_order() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let uuid = "00112233-4455-6677-8899-aabbccddeeff";
+    let guid: Vec<u8> = vec![
+        0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff,
+    ];
+
+    let to_guid: Vec<u8> = conn.query_row("SELECT uuid_to_guid(?1)", [uuid], |r| r.get(0)).unwrap();
+    assert_eq!(to_guid, guid);
+    let to_guid: Vec<u8> =
+        conn.query_row("SELECT uuid_to_guid(uuid_blob(?1))", [uuid], |r| r.get(0)).unwrap();
+    assert_eq!(to_guid, guid);
+
+    let to_uuid: String =
+        conn.query_row("SELECT uuid_str(uuid_guid_to_uuid(?1))", [&guid], |r| r.get(0)).unwrap();
+    assert_eq!(to_uuid, uuid);
+
+    // Both directions round-trip.
+    let round_trip: Vec<u8> = conn
+        .query_row("SELECT uuid_to_guid(uuid_guid_to_uuid(?1))", [&guid], |r| r.get(0))
+        .unwrap();
+    assert_eq!(round_trip, guid);
+    let round_trip: String = conn
+        .query_row("SELECT uuid_str(uuid_guid_to_uuid(uuid_to_guid(?1)))", [uuid], |r| r.get(0))
+        .unwrap();
+    assert_eq!(round_trip, uuid);
+
+    for sql in [
+        "SELECT uuid_guid_to_uuid(x'0011')",
+        "SELECT uuid_guid_to_uuid('00112233-4455-6677-8899-aabbccddeeff')",
+        "SELECT uuid_to_guid('not-a-uuid')",
+    ] {
+        let invalid: Option<Vec<u8>> = conn.query_row(sql, [], |r| r.get(0)).unwrap();
+        assert_eq!(invalid, None, "{sql}");
+    }
+
+    // The strict entry point raises an error instead.
+    let strict = Connection::open_in_memory().unwrap();
+    let rc = unsafe {
+        sqlite_wasm_uuid_rs::sqlite3_uuid_init_strict(
+            strict.handle().cast(),
+            core::ptr::null_mut(),
+            core::ptr::null(),
+        )
+    };
+    assert_eq!(rc, rusqlite::ffi::SQLITE_OK);
+    assert!(strict
+        .query_row("SELECT uuid_to_guid('not-a-uuid')", [], |r| r.get::<_, Option<Vec<u8>>>(0))
+        .is_err());
+    assert_eq!(
+        strict
+            .query_row("SELECT uuid_to_guid(NULL)", [], |r| r.get::<_, Option<Vec<u8>>>(0))
+            .unwrap(),
+        None
+    );
+}
+
+/// Tests the `uuid_series` table-valued function.
+#[wasm_bindgen_test]
+fn test_uuid_series() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    let count = |sql: &str| -> i64 { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+
+    assert_eq!(count("SELECT count(DISTINCT uuid) FROM uuid_series(100)"), 100);
+    assert_eq!(count("SELECT count(*) FROM uuid_series(100) WHERE uuid_version(uuid) = 4"), 100);
+    assert_eq!(count("SELECT count(*) FROM uuid_series(10, 7) WHERE uuid_version(uuid) = 7"), 10);
+    assert_eq!(count("SELECT count(*) FROM uuid_series(0)"), 0);
+    assert_eq!(count("SELECT count(*) FROM uuid_series(-3)"), 0);
+    assert_eq!(count("SELECT count(*) FROM uuid_series(NULL)"), 0);
+    assert_eq!(count("SELECT count(*) FROM uuid_series"), 0);
+
+    // UUIDv7 come out in generation order.
+    let mut stmt = conn.prepare("SELECT uuid FROM uuid_series(50, 7) ORDER BY rowid").unwrap();
+    let uuids: Vec<String> =
+        stmt.query_map([], |r| r.get(0)).unwrap().collect::<Result<_, _>>().unwrap();
+    assert!(uuids.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // A row keeps its UUID when its column is read several times.
+    assert_eq!(count("SELECT count(*) FROM uuid_series(20) WHERE uuid = uuid"), 20);
+
+    // Works as the source of a bulk insert, and on every row of a join.
+    conn.execute_batch("CREATE TABLE t(g INTEGER, id TEXT PRIMARY KEY)").unwrap();
+    conn.execute_batch(
+        "INSERT INTO t SELECT g.v, s.uuid
+         FROM (SELECT 4 AS v UNION ALL SELECT 7) AS g, uuid_series(5, g.v) AS s",
+    )
+    .unwrap();
+    assert_eq!(count("SELECT count(*) FROM t WHERE uuid_version(id) = g"), 10);
+
+    let error = conn.query_row("SELECT uuid FROM uuid_series(1, 5)", [], |r| r.get::<_, String>(0));
+    assert!(error.unwrap_err().to_string().contains("version must be 4 or 7"));
+}
+
+/// Tests the `uuid8` functions, available with the `v8` feature.
+#[cfg(feature = "v8")]
+#[wasm_bindgen_test]
+fn test_uuid8() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+
+    // An INTEGER fills the high 48 bits, the other custom bits are random.
+    let (a, b): (String, String) = conn
+        .query_row("SELECT uuid8(0x0123456789ab), uuid8(0x0123456789ab)", [], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .unwrap();
+    let a = Uuid::parse_str(&a).unwrap();
+    let b = Uuid::parse_str(&b).unwrap();
+    assert_eq!(a.get_version_num(), 8);
+    assert_eq!(a.get_variant(), uuid::Variant::RFC4122);
+    assert_eq!(a.as_bytes()[..6], [0x01, 0x23, 0x45, 0x67, 0x89, 0xab]);
+    assert_eq!(a.as_bytes()[..6], b.as_bytes()[..6]);
+    assert_ne!(a, b);
+
+    // A 16-byte BLOB provides all the bits but the version and variant.
+    let blob: Vec<u8> = conn
+        .query_row("SELECT uuid8_blob(x'ffffffffffffffffffffffffffffffff')", [], |r| r.get(0))
+        .unwrap();
+    let u = Uuid::from_slice(&blob).unwrap();
+    assert_eq!(u.get_version_num(), 8);
+    assert_eq!(u.to_string(), "ffffffff-ffff-8fff-bfff-ffffffffffff");
+
+    for sql in [
+        "SELECT uuid8(-1)",
+        "SELECT uuid8(0x1000000000000)",
+        "SELECT uuid8(x'0011')",
+        "SELECT uuid8('not-a-blob')",
+        "SELECT uuid8(NULL)",
+    ] {
+        let invalid: Option<String> = conn.query_row(sql, [], |r| r.get(0)).unwrap();
+        assert_eq!(invalid, None, "{sql}");
+    }
+}
+
+/// Tests that `uuid_eq` joins TEXT and BLOB forms of the same UUID.
+#[wasm_bindgen_test]
+fn test_uuid_eq() {
+    unsafe {
+        sqlite_wasm_uuid_rs::register().expect("Failed to register sqlite-wasm-uuid-rs");
+    }
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE users(id TEXT, name TEXT);
+         CREATE TABLE posts(author_id BLOB, title TEXT);
+         INSERT INTO users VALUES ('017F22E2-79B0-7CC3-98C4-DC0C0C07398F', 'alice'), (uuid(), 'bob');
+         INSERT INTO posts VALUES (uuid_blob('017f22e2-79b0-7cc3-98c4-dc0c0c07398f'), 'hello');",
+    )
+    .unwrap();
+
+    // A plain equality never matches a TEXT against a BLOB.
+    let plain: i64 = conn
+        .query_row("SELECT count(*) FROM users JOIN posts ON users.id = posts.author_id", [], |r| {
+            r.get(0)
+        })
+        .unwrap();
+    assert_eq!(plain, 0);
+
+    let mut stmt = conn
+        .prepare("SELECT name, title FROM users JOIN posts ON uuid_eq(users.id, posts.author_id)")
+        .unwrap();
+    let rows: Vec<(String, String)> = stmt
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(rows, [("alice".to_string(), "hello".to_string())]);
+
+    let eq = |sql: &str| -> Option<i64> { conn.query_row(sql, [], |r| r.get(0)).unwrap() };
+    assert_eq!(eq("SELECT uuid_eq(uuid_nil(), uuid_max())"), Some(0));
+    assert_eq!(eq("SELECT uuid_eq(uuid_nil(), '00000000000000000000000000000000')"), Some(1));
+    assert_eq!(eq("SELECT uuid_eq(uuid_nil(), 'not-a-uuid')"), None);
+    assert_eq!(eq("SELECT uuid_eq(NULL, uuid_nil())"), None);
+}